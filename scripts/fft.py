@@ -1,4 +1,4 @@
-from scipy.fft import fft, fftfreq
+from scipy.fft import fft, fftfreq, dct, dst
 import numpy as np 
 import matplotlib.pyplot as plt
 from common import PathManage, Description, gen_sine_data, write_as_json, gen_complex_exp_data
@@ -87,6 +87,24 @@ def gen_fftfreq_data():
     desc = Description(input_data=input_data, output_data=output_data, func=func, path=PATH, ienum="FftFreqVals", oenum="Array")
     write_as_json(desc)
 
+
+def gen_trig_transform_data():
+
+    # Arbitrary values, the length is not a power of two to exercise the 
+    # non radix-2 code paths
+    fsine = 2.0
+    fsample = 100.0
+    duration = 1.0
+
+    input_data = gen_sine_data(fsine, fsample, duration) + 0.5
+    for name, transform in (("dct", dct), ("dst", dst)):
+        for dct_type in range(1, 5):
+            for norm in ("backward", "ortho"):
+                func = f"{name}{dct_type}" if norm == "backward" else f"{name}{dct_type}_ortho"
+                output_data = transform(input_data, type=dct_type, norm=norm)
+                desc = Description(input_data=input_data, output_data=output_data, func=func, path=PATH, ienum="Array", oenum="Array")
+                write_as_json(desc)
+
      
 def gen_zero_pad_data():
     pass
@@ -94,6 +112,7 @@ def gen_zero_pad_data():
 def main(plot):
     gen_fft_sine_data(plot=plot)
     gen_fftfreq_data()
+    gen_trig_transform_data()
     gen_zero_pad_data()
     gen_fft_complex_exp_data(plot=plot)
 
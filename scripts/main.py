@@ -1,4 +1,4 @@
-import wavegen, fft, os
+import wavegen, fft, spectral, os
 from common import argparse_setup
 from pathlib import Path
 
@@ -9,6 +9,8 @@ if __name__ == '__main__':
     os.mkdir(datasets)
     os.mkdir(datasets / 'fft')
     os.mkdir(datasets / 'wavegen')
+    os.mkdir(datasets / 'spectral')
 
     wavegen.main(args.plot)
-    fft.main(args.plot)
\ No newline at end of file
+    fft.main(args.plot)
+    spectral.main(args.plot)
\ No newline at end of file
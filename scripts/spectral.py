@@ -0,0 +1,114 @@
+from scipy import signal
+import numpy as np
+from common import Description, write_as_json
+
+PATH = "datasets/spectral"
+
+def gen_spectral_signal(fs: float, n: int):
+    # Two tones and an offset so the segments have a nonzero mean
+    t = np.arange(n) / fs
+    return np.sin(2 * np.pi * 5.0 * t) + 0.5 * np.cos(2 * np.pi * 17.0 * t) + 0.2
+
+def gen_stft_data():
+    # Arbitrary values, the second dataset has an odd segment and transform length
+    fs = 100.0
+    x = gen_spectral_signal(fs, 230)
+    for func, (nperseg, noverlap, nfft) in (("stft", (32, 24, 40)), ("stft_odd", (25, 10, 31))):
+        _, _, zxx = signal.stft(x, fs=fs, window="hann", nperseg=nperseg, noverlap=noverlap, nfft=nfft)
+        # Stored as segments x bins
+        zxx = zxx.T.ravel()
+        input_data = {"x": x.tolist(), "fs": fs, "nperseg": nperseg, "noverlap": noverlap, "nfft": nfft}
+        output_data = {"mag": np.abs(zxx).tolist(), "phase": np.angle(zxx).tolist()}
+        desc = Description(input_data=input_data, output_data=output_data, func=func, path=PATH, ienum="SegmentVals", oenum="ComplexVals")
+        write_as_json(desc)
+
+def gen_spectrogram_data():
+    # Arbitrary values covering the scaling, mode, detrend and one-sided options
+    fs = 100.0
+    x = gen_spectral_signal(fs, 230)
+    params = {
+        "spectrogram_psd": ((32, 8, 32), dict(detrend="constant", return_onesided=True, scaling="density", mode="psd")),
+        "spectrogram_spectrum": ((25, 10, 31), dict(detrend=False, return_onesided=True, scaling="spectrum", mode="psd")),
+        "spectrogram_magnitude": ((32, 16, 40), dict(detrend="constant", return_onesided=True, scaling="density", mode="magnitude")),
+        "spectrogram_two_sided": ((30, 12, 30), dict(detrend="linear", return_onesided=False, scaling="density", mode="psd")),
+    }
+    for func, ((nperseg, noverlap, nfft), kwargs) in params.items():
+        _, _, sxx = signal.spectrogram(x, fs=fs, window="hann", nperseg=nperseg, noverlap=noverlap, nfft=nfft, **kwargs)
+        input_data = {"x": x.tolist(), "fs": fs, "nperseg": nperseg, "noverlap": noverlap, "nfft": nfft}
+        # Stored as segments x bins
+        desc = Description(input_data=input_data, output_data=sxx.T.ravel(), func=func, path=PATH, ienum="SegmentVals", oenum="Array")
+        write_as_json(desc)
+
+def gen_welch_data():
+    # Arbitrary values covering the scaling, averaging, detrend and one-sided options
+    fs = 100.0
+    x = gen_spectral_signal(fs, 230)
+    params = {
+        "welch": ((32, 16, 32), dict(detrend="constant", return_onesided=True, scaling="density", average="mean")),
+        "welch_median": ((25, 12, 31), dict(detrend="linear", return_onesided=True, scaling="spectrum", average="median")),
+        "welch_two_sided": ((40, 20, 48), dict(detrend=False, return_onesided=False, scaling="density", average="mean")),
+    }
+    for func, ((nperseg, noverlap, nfft), kwargs) in params.items():
+        _, pxx = signal.welch(x, fs=fs, window="hann", nperseg=nperseg, noverlap=noverlap, nfft=nfft, **kwargs)
+        input_data = {"x": x.tolist(), "fs": fs, "nperseg": nperseg, "noverlap": noverlap, "nfft": nfft}
+        desc = Description(input_data=input_data, output_data=pxx, func=func, path=PATH, ienum="SegmentVals", oenum="Array")
+        write_as_json(desc)
+
+def gen_periodogram_data():
+    # The first periodogram is zero padded and the second truncated to nfft samples
+    fs = 100.0
+    x = gen_spectral_signal(fs, 230)
+    params = {
+        "periodogram": (256, dict(window="boxcar", detrend="constant", return_onesided=True, scaling="density")),
+        "periodogram_truncated": (200, dict(window="hann", detrend="linear", return_onesided=False, scaling="spectrum")),
+    }
+    for func, (nfft, kwargs) in params.items():
+        _, pxx = signal.periodogram(x, fs=fs, nfft=nfft, **kwargs)
+        nperseg = min(nfft, len(x))
+        input_data = {"x": x.tolist(), "fs": fs, "nperseg": nperseg, "noverlap": 0, "nfft": nfft}
+        desc = Description(input_data=input_data, output_data=pxx, func=func, path=PATH, ienum="SegmentVals", oenum="Array")
+        write_as_json(desc)
+
+def gen_system_signal(x: np.ndarray, fs: float):
+    # A two tap filter of the input and a tone uncorrelated with the input
+    t = np.arange(len(x)) / fs
+    return signal.lfilter([0.5, 0.3], [1.0], x) + 0.2 * np.sin(2 * np.pi * 23.0 * t)
+
+def gen_csd_data():
+    # Arbitrary values covering the scaling, averaging, detrend and one-sided options
+    fs = 100.0
+    x = gen_spectral_signal(fs, 230)
+    y = gen_system_signal(x, fs)
+    params = {
+        "csd": ((32, 16, 32), dict(detrend="constant", return_onesided=True, scaling="density", average="mean")),
+        "csd_median_two_sided": ((25, 12, 31), dict(detrend="linear", return_onesided=False, scaling="spectrum", average="median")),
+    }
+    for func, ((nperseg, noverlap, nfft), kwargs) in params.items():
+        _, pxy = signal.csd(x, y, fs=fs, window="hann", nperseg=nperseg, noverlap=noverlap, nfft=nfft, **kwargs)
+        input_data = {"x": x.tolist(), "y": y.tolist(), "fs": fs, "nperseg": nperseg, "noverlap": noverlap, "nfft": nfft}
+        output_data = {"mag": np.abs(pxy).tolist(), "phase": np.angle(pxy).tolist()}
+        desc = Description(input_data=input_data, output_data=output_data, func=func, path=PATH, ienum="CrossSegmentVals", oenum="ComplexVals")
+        write_as_json(desc)
+
+def gen_coherence_data():
+    fs = 100.0
+    nperseg, noverlap, nfft = 32, 16, 40
+    x = gen_spectral_signal(fs, 230)
+    y = gen_system_signal(x, fs)
+    _, cxy = signal.coherence(x, y, fs=fs, window="hann", nperseg=nperseg, noverlap=noverlap, nfft=nfft, detrend="constant")
+    input_data = {"x": x.tolist(), "y": y.tolist(), "fs": fs, "nperseg": nperseg, "noverlap": noverlap, "nfft": nfft}
+    desc = Description(input_data=input_data, output_data=cxy, func="coherence", path=PATH, ienum="CrossSegmentVals", oenum="Array")
+    write_as_json(desc)
+
+def main(plot):
+    gen_stft_data()
+    gen_spectrogram_data()
+    gen_welch_data()
+    gen_periodogram_data()
+    gen_csd_data()
+    gen_coherence_data()
+
+if __name__ == "__main__":
+    from common import argparse_setup
+    args = argparse_setup()
+    main(args.plot)
@@ -101,12 +101,37 @@ def gen_pulse_wave_data():
     pass
 
 def gen_window_data():
-    pass
+    # Arbitrary values, the even length exercises the odd and even length code
+    # paths of the symmetric and periodic windows
+    n = 50
+    params = {
+        "boxcar": None,
+        "hann": None,
+        "hamming": None,
+        "blackman": None,
+        "blackmanharris": None,
+        "nuttall": None,
+        "flattop": None,
+        "bartlett": None,
+        "tukey": 0.5,
+        "kaiser": 8.6,
+        "gaussian": 7.0,
+        "chebwin": 100.0,
+    }
+    for name, param in params.items():
+        for sym in (True, False):
+            func = name if sym else f"{name}_periodic"
+            args = () if param is None else (param,)
+            output_data = getattr(windows, name)(n, *args, sym=sym)
+            input_data = {"n": n, "param": 0.0 if param is None else param}
+            desc = Description(input_data=input_data, output_data=output_data, func=func, path=PATH, ienum="WindowVals", oenum="Array")
+            write_as_json(desc)
 
 def main(plot):
     gen_raw_sine_data(plot=plot)
     gen_sine_freq_data(plot=plot)
     gen_sinc_data(plot=plot)
+    gen_window_data()
 
 if __name__ == "__main__":
     from common import argparse_setup
@@ -0,0 +1,46 @@
+//! The error type returned by the fallible `try_` functions of the crate
+use core::fmt;
+
+/// Errors returned by fallible transforms and collection utilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RufftError {
+    /// The input collection has no items
+    EmptyInput,
+    /// A collection does not have the length required by the operation
+    LengthMismatch { expected: usize, actual: usize },
+    /// The algorithm does not support transforms of this length
+    UnsupportedLength(usize),
+    /// A parameter is out of its valid range, contains the parameter name
+    InvalidParameter(&'static str),
+}
+
+impl fmt::Display for RufftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyInput => write!(f, "The input collection is empty"),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "Expected a length of {}, got {}", expected, actual)
+            },
+            Self::UnsupportedLength(n) => write!(f, "Unsupported transform length {}", n),
+            Self::InvalidParameter(name) => write!(f, "Invalid value for parameter `{}`", name),
+        }
+    }
+}
+
+impl core::error::Error for RufftError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(RufftError::EmptyInput.to_string(), "The input collection is empty");
+        assert_eq!(
+            RufftError::LengthMismatch { expected: 8, actual: 5 }.to_string(),
+            "Expected a length of 8, got 5"
+        );
+        assert_eq!(RufftError::UnsupportedLength(11).to_string(), "Unsupported transform length 11");
+        assert_eq!(RufftError::InvalidParameter("len").to_string(), "Invalid value for parameter `len`");
+    }
+}
@@ -4,7 +4,10 @@
 //! | Algorithm | Module Name |
 //! | --------- | ----------- |
 //! | Cooley-Tukey | `ct` | 
+//! | Mixed-Radix Cooley-Tukey | `mixed` | 
 //! | Chirp-Z Transform (Bluestein's Algorithm) | `czt` | 
+//! | Rader's Algorithm (prime lengths) | `rader` | 
+//! | Good-Thomas Prime Factor Algorithm | `pfa` | 
 //! 
 //! The most common use case tends to be computing the FFT of a real-valued input collection
 //! producting a complex output collection. The opposite for computing the IFFT. Thus,
@@ -16,14 +19,215 @@
 //! * `ct::complex::fft`
 //! * `ct::complex::ifft`
 //! 
+//! Inverse transforms are normalized by `1/n` by default. The `dft`, `ct`, `mixed`
+//! and `czt` transforms, the `real` and `nd` transforms and the transform traits
+//! also have `_norm` variants, e.g. `ct::fft_norm`, which accept a `Normalization`
+//! equivalent to the `norm` argument of numpy and scipy, as do plans created with
+//! `Plan::with_normalization`. The `rader` and `pfa` transforms and the chirp-z
+//! transforms along general contours, `czt::czt` and `czt::zoom_fft`, only use
+//! the default normalization.
+//! 
+//! The `real` module provides `rfft` and `irfft` functions for real valued signals
+//! which only compute the `n / 2 + 1` non-redundant bins of the spectrum.
+//! 
+//! The discrete cosine and sine transforms of types I to IV are provided by the 
+//! `dct` and `dst` modules.
+//! 
+//! Multidimensional transforms of `ndarray` arrays are provided by the `nd` module
+//! when the `ndarray` feature is enabled.
+//! 
+//! When many collections of the same length are transformed the `plan` module
+//! provides reusable plans which precompute the twiddle factors and scratch space.
+//! The free functions and the `Fft` traits recompute the twiddle factors, and
+//! for Bluestein's algorithm the chirps and their spectrum, on every call, so
+//! only `Plan::process` avoids that work for repeated transforms.
+//! The `fixed` module provides transforms of lengths known at compile time whose
+//! twiddle factors are computed during compilation, and the `fixed_point` module
+//! provides transforms of Q15 and Q31 integer samples for targets without an FPU.
+//! 
 pub mod ct;
 pub mod czt;
+pub mod mixed;
 pub mod complex;
+pub mod fixed;
+pub mod fixed_point;
+#[cfg(feature = "std")]
+pub mod plan;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+pub mod rader;
+#[cfg(feature = "std")]
+pub mod pfa;
+pub mod real;
+#[cfg(all(feature = "ndarray", feature = "std"))]
+pub mod nd;
+#[cfg(feature = "std")]
+pub mod dct;
+#[cfg(feature = "std")]
+pub mod dst;
 use num_integer::Integer;
 use num_complex::Complex;
 use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive, NumAssignOps };
 use core::ops::IndexMut;
-use crate::traits::Iterable;
+use crate::error::RufftError;
+use crate::traits::{ Iterable, Signal };
+
+/// Normalization mode of a transform pair, equivalent to the `norm` argument of
+/// numpy and scipy's fft functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Normalization {
+    /// No scaling of the forward transform and `1/n` scaling of the inverse
+    #[default]
+    Backward,
+    /// `1/sqrt(n)` scaling of both the forward and inverse transforms
+    Ortho,
+    /// `1/n` scaling of the forward transform and no scaling of the inverse
+    Forward,
+}
+
+impl Normalization {
+    /// The normalization mode of the opposite direction which gives the same
+    /// scaling e.g. scipy's `idct(x, norm="backward")` is `dct(x, norm="forward")`
+    /// of the inverse type
+    pub fn swap(self) -> Self {
+        match self {
+            Self::Backward => Self::Forward,
+            Self::Ortho => Self::Ortho,
+            Self::Forward => Self::Backward,
+        }
+    }
+
+    /// The scale factor applied to the output of a forward transform of length `n`
+    pub fn forward_scale<F>(self, n: usize) -> F
+    where
+        F: Float + 'static,
+        usize: AsPrimitive<F>,
+    {
+        match self {
+            Self::Backward => F::one(),
+            Self::Ortho => F::one() / n.as_().sqrt(),
+            Self::Forward => F::one() / n.as_(),
+        }
+    }
+
+    /// The scale factor applied to the output of an inverse transform of length `n`
+    pub fn inverse_scale<F>(self, n: usize) -> F
+    where
+        F: Float + 'static,
+        usize: AsPrimitive<F>,
+    {
+        self.swap().forward_scale(n)
+    }
+}
+
+/// Zero pads or truncates the items of the iterator to exactly `n` items
+pub(crate) fn resize<T: Clone>(x: impl Iterator<Item = T>, fill: T, n: usize) -> impl Iterator<Item = T> {
+    x.chain(core::iter::repeat(fill)).take(n)
+}
+
+/// Multiplies every item of the complex valued slice by the real valued factor
+pub(crate) fn scale<F: Float>(x: &mut [Complex<F>], factor: F) {
+    if factor != F::one() {
+        x.iter_mut().for_each(|v| *v = v.scale(factor));
+    }
+}
+
+/// The length of the scratch buffer required by `fft_in_place_with_scratch` for 
+/// a transform of length `n`. Powers of two need no scratch space, mixed-radix
+/// lengths need `2n` and other lengths need two power of two buffers for 
+/// Bluestein's algorithm
+pub fn scratch_len(n: usize) -> usize {
+    if n <= 1 || n.is_power_of_two() {
+        0
+    } else if mixed::is_supported_len(n) {
+        2 * n
+    } else {
+        2 * (2 * n - 1).next_power_of_two()
+    }
+}
+
+/// Computes the fast fourier transform of the complex valued slice in-place 
+/// without allocating. Only lengths which need no scratch space, i.e. powers 
+/// of two, are supported, other lengths return `RufftError::UnsupportedLength`
+/// and should use `fft_in_place_with_scratch`
+pub fn fft_in_place<F>(buf: &mut [Complex<F>]) -> Result<(), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    if scratch_len(buf.len()) > 0 {
+        return Err(RufftError::UnsupportedLength(buf.len()));
+    }
+    fft_in_place_with_scratch(buf, &mut [])
+}
+
+/// Computes the inverse fast fourier transform of the complex valued slice 
+/// in-place without allocating. Only lengths which need no scratch space are 
+/// supported, as for `fft_in_place`.
+/// The output *is* normalized
+pub fn ifft_in_place<F>(buf: &mut [Complex<F>]) -> Result<(), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    if scratch_len(buf.len()) > 0 {
+        return Err(RufftError::UnsupportedLength(buf.len()));
+    }
+    ifft_in_place_with_scratch(buf, &mut [])
+}
+
+/// Computes the fast fourier transform of the complex valued slice of any length
+/// in-place without allocating, using the caller provided scratch space which
+/// must hold at least `scratch_len(buf.len())` items
+pub fn fft_in_place_with_scratch<F>(buf: &mut [Complex<F>], scratch: &mut [Complex<F>]) -> Result<(), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = buf.len();
+    let required = scratch_len(n);
+    if scratch.len() < required {
+        return Err(RufftError::LengthMismatch { expected: required, actual: scratch.len() });
+    }
+    if n <= 1 {
+        return Ok(());
+    }
+    if n.is_power_of_two() {
+        ct::complex::radix2_in_place(buf, -F::one());
+    } else if mixed::is_supported_len(n) {
+        let (input, twiddles) = scratch[..required].split_at_mut(n);
+        input.copy_from_slice(buf);
+        twiddles.iter_mut()
+            .zip(mixed::complex::twiddles(n))
+            .for_each(|(t, w)| *t = w);
+        mixed::complex::process(input, buf, 1, twiddles);
+    } else {
+        czt::bluestein_in_place(buf, scratch);
+    }
+    Ok(())
+}
+
+/// Computes the inverse fast fourier transform of the complex valued slice of 
+/// any length in-place without allocating, using the caller provided scratch 
+/// space which must hold at least `scratch_len(buf.len())` items.
+/// The output *is* normalized
+pub fn ifft_in_place_with_scratch<F>(buf: &mut [Complex<F>], scratch: &mut [Complex<F>]) -> Result<(), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let required = scratch_len(buf.len());
+    if scratch.len() < required {
+        return Err(RufftError::LengthMismatch { expected: required, actual: scratch.len() });
+    }
+    // The inverse transform is computed as conj(fft(conj(x))) / n
+    buf.iter_mut().for_each(|v| *v = v.conj());
+    fft_in_place_with_scratch(buf, scratch)?;
+    let n: F = buf.len().as_();
+    buf.iter_mut().for_each(|v| *v = v.conj() / n);
+    Ok(())
+}
 
 /// Compute the discrete time fourier transform of the real valued input collection.
 /// Returns a closure which accepts a collection of sample frequencies and returns 
@@ -57,25 +261,50 @@ where
     for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
     usize: AsPrimitive<F>,
 {
-    complex::idft_internal(x).iter().map(|x| x.re).collect()
+    idft_norm(x, Normalization::Backward)
+}
+
+/// Computes the inverse discrete fourier transform of the real valued input 
+/// collection with the given normalization
+pub fn idft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    complex::idft_internal(x, norm).iter().map(|x| x.re).collect()
 }
 
 /// Computes the discrete fourier tranform on the real valued input collection
 pub fn dft<F, I, C>(x: &I) -> C
 where
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    usize: AsPrimitive<F>,
+{
+    dft_norm(x, Normalization::Backward)
+}
+
+/// Computes the discrete fourier tranform on the real valued input collection
+/// with the given normalization
+pub fn dft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
     for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     usize: AsPrimitive<F>,
 {
     let n = x.len();
+    let scale: F = norm.forward_scale(n);
     let zero = F::zero();
     let twopi = F::TAU();
-    x.iter().enumerate().map(|(i, _)|{ // Change to a range of some kind
-        x.iter().enumerate().map(|(j, f)| {
+    x.samples().enumerate().map(|(i, _)|{ // Change to a range of some kind
+        x.samples().enumerate().map(|(j, f)| {
             let phase = Complex::<F>::new(zero, -(twopi * j.as_() * i.as_()) / n.as_());
             Complex::<F>::new(*f, zero) * phase.exp()
-        }).sum()
+        }).sum::<Complex<F>>() * scale
     }).collect()
 }
 
@@ -180,6 +409,75 @@ mod tests {
         test_idft!(f64, Array1<Complex<f64>>, Array1<f64>, RTOL_F64, ATOL_F64);
     }
 
+    #[test]
+    fn test_fft_in_place_with_scratch() {
+        for n in [1, 8, 12, 210, 11, 127, 1000, 1009] {
+            let x: Vec<Complex<f64>> = (0..n)
+                .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+                .collect();
+            let reference: Vec<Complex<f64>> = complex::dft(&x);
+            let mut buf = x.clone();
+            let mut scratch = vec![Complex::new(0.0, 0.0); scratch_len(n)];
+            fft_in_place_with_scratch(&mut buf, &mut scratch).unwrap();
+            for (i, (&a, &b)) in buf.iter().zip(reference.iter()).enumerate() {
+                assert!(test::nearly_equal_complex(a, b, RTOL_F64, 1e-10), "n = {}, {} => {} != {}", n, i, a, b);
+            }
+            ifft_in_place_with_scratch(&mut buf, &mut scratch).unwrap();
+            for (i, (&a, &b)) in buf.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal_complex(a, b, RTOL_F64, 1e-10), "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_in_place_errors() {
+        let mut buf = [Complex::new(1.0, 0.0); 12];
+        assert_eq!(fft_in_place(&mut buf), Err(RufftError::UnsupportedLength(12)));
+        let mut scratch = [Complex::new(0.0, 0.0); 10];
+        assert_eq!(fft_in_place_with_scratch(&mut buf, &mut scratch), Err(RufftError::LengthMismatch { expected: 24, actual: 10 }));
+        let mut buf = [Complex::new(1.0, 0.0); 16];
+        assert_eq!(fft_in_place(&mut buf), Ok(()));
+        assert_eq!(ifft_in_place(&mut buf), Ok(()));
+    }
+
+    #[test]
+    fn test_ifft_in_place_small_scratch() {
+        // A failed transform leaves the buffer unchanged
+        let mut buf: Vec<Complex<f64>> = (0..12).map(|i| Complex::new(i as f64, -(i as f64))).collect();
+        let original = buf.clone();
+        let mut scratch = [Complex::new(0.0, 0.0); 10];
+        assert_eq!(ifft_in_place_with_scratch(&mut buf, &mut scratch), Err(RufftError::LengthMismatch { expected: 24, actual: 10 }));
+        assert_eq!(buf, original);
+    }
+
+    #[test]
+    fn test_normalization_scales() {
+        let n = 16;
+        assert_eq!(Normalization::Backward.forward_scale::<f64>(n), 1.0);
+        assert_eq!(Normalization::Backward.inverse_scale::<f64>(n), 1.0 / 16.0);
+        assert_eq!(Normalization::Ortho.forward_scale::<f64>(n), 0.25);
+        assert_eq!(Normalization::Ortho.inverse_scale::<f64>(n), 0.25);
+        assert_eq!(Normalization::Forward.forward_scale::<f64>(n), 1.0 / 16.0);
+        assert_eq!(Normalization::Forward.inverse_scale::<f64>(n), 1.0);
+    }
+
+    #[test]
+    fn test_dft_idft_norm_roundtrip() {
+        let x: Vec<f64> = (0..30).map(|i| (i as f64 * 0.4).sin()).collect();
+        for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+            let spectrum: Vec<Complex<f64>> = dft_norm(&x, norm);
+            let y: Vec<f64> = idft_norm(&spectrum, norm);
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+            }
+        }
+        // The orthonormal transform preserves the energy of the signal
+        let spectrum: Vec<Complex<f64>> = dft_norm(&x, Normalization::Ortho);
+        let energy: f64 = x.iter().map(|v| v * v).sum();
+        let spectrum_energy: f64 = spectrum.iter().map(|v| v.norm_sqr()).sum();
+        assert!(test::nearly_equal(energy, spectrum_energy, RTOL_F64, ATOL_F64));
+    }
+
    #[test]
     fn test_fftfreq_balanced() {
         let json_data: Json<f64> = read_json("datasets/fft/fftfreq/fftfreq.json");
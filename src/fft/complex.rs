@@ -1,15 +1,28 @@
 use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
 use num_complex::Complex;
+use crate::fft::Normalization;
 use crate::traits::Iterable;
 
 /// Compute the discrete fourier transform on the complex valued input collection
 pub fn dft<F, I>(x: &I) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    usize: AsPrimitive<F>,
+{
+    dft_norm(x, Normalization::Backward)
+}
+
+/// Compute the discrete fourier transform on the complex valued input collection
+/// with the given normalization
+pub fn dft_norm<F, I>(x: &I, norm: Normalization) -> I
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     usize: AsPrimitive<F>,
 {
     let n = x.len();
+    let scale: F = norm.forward_scale(n);
     let zero = F::zero();
     //let complex_zero = Complex::new(zero, zero);
     let twopi = F::TAU();
@@ -17,26 +30,27 @@ where
         x.iter().enumerate().map(|(j, &f)| {
             let phase = Complex::<F>::new(zero, -(twopi * j.as_() * i.as_()) / n.as_());
             f * phase.exp()
-        }).sum()
+        }).sum::<Complex<F>>() * scale
     }).collect()
 }
 
-/// Internal inverse discrete fourier transfom which returns a complex collection.
-/// Note that the output *is* normalized
-pub(crate) fn idft_internal<'a, F, I>(x: &'a I) -> I
+/// Internal inverse discrete fourier transfom which returns a complex collection
+/// scaled according to the given normalization
+pub(crate) fn idft_internal<'a, F, I>(x: &'a I, norm: Normalization) -> I
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     usize: AsPrimitive<F>,
 {
     let n = x.len();
+    let scale: F = norm.inverse_scale(n);
     let zero = F::zero();
     let twopi = F::TAU();
     x.iter().enumerate().map(|(i, _)|{
         x.iter().enumerate().map(|(j, f)| {
             let phase = Complex::<F>::new(zero, (twopi * j.as_() * i.as_()) / n.as_());
             *f * phase.exp()
-        }).map(|v| v).sum::<Complex<F>>() / n.as_()
+        }).sum::<Complex<F>>() * scale
     }).collect()
 }
 
@@ -49,7 +63,18 @@ where
     for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     usize: AsPrimitive<F>,
 {
-    idft_internal(x)
+    idft_internal(x, Normalization::Backward)
+}
+
+/// Computes the inverse discrete fourier transform on the complex valued input 
+/// collection with the given normalization returning a complex output collection
+pub fn idft_norm<F, I>(x: &I, norm: Normalization) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    usize: AsPrimitive<F>,
+{
+    idft_internal(x, norm)
 }
 
 #[cfg(test)]
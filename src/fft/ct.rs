@@ -1,53 +1,61 @@
 pub mod complex;
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+mod simd;
 use num_complex::Complex;
 use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
-use core::ops::IndexMut;
-use itertools::izip;
-use crate::traits::Iterable;
+use crate::error::RufftError;
+use crate::fft::{ resize, Normalization };
+use crate::traits::{ Iterable, IterableMut, Signal };
 
 /// Computes the cooley-tukey fast fourier transform of the real valued input 
 /// collection
 pub fn fft<F, I, C>(x: &I) -> C
 where
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: Signal<Sample = F> + ?Sized,
     for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
-    C: IndexMut<usize, Output = Complex<F>>,
+    C: IterableMut,
     usize: AsPrimitive<F>,
 {
-    let n = x.len();
-    let zero = F::zero();
-    let one = F::one();
-    let twopi = F::TAU();
-
-    if n == 1 {
-        x.iter()
-            .map(|&x| Complex::new(x, zero))
-            .collect()
-    } else {
-        let wn = Complex::new(zero, -twopi / (n.as_()));
-        let wn = wn.exp();
-        let mut w = Complex::new(one, zero);
-
-        let x_even: I = x.iter().step_by(2).cloned().collect();
-        let x_odd: I = x.iter().skip(1).step_by(2).cloned().collect();
-
-        let y_even: C = fft(&x_even);
-        let y_odd: C = fft(&x_odd);
+    fft_norm(x, Normalization::Backward)
+}
 
-        let mut y = C::from_iter(core::iter::repeat(Complex::new(zero, zero)).take(n));
-        izip!(y_even.iter(), y_odd.iter())
-            .enumerate()
-            .for_each(|(j, (even, odd))| {
-                let tmp = w * odd;
-                y[j] = even + tmp;
-                y[j + n / 2] = even - tmp;
-                w *= wn;
-            });
-        y
-    }
+/// Computes the cooley-tukey fast fourier transform of the real valued input 
+/// collection with the given normalization
+pub fn fft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let mut y: C = x.samples()
+        .map(|&x| Complex::new(x, zero))
+        .collect();
+    complex::fft_in_place_norm(y.as_mut_slice(), norm);
+    y
 }
 
+/// Computes the cooley-tukey fast fourier transform of length `n` of the real 
+/// valued input collection, which is zero padded or truncated to length `n`.
+/// Equivalent to numpy's `fft(x, n)`, `n` must be a power of two
+pub fn fft_n<F, I, C>(x: &I, n: usize) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let mut y: C = resize(x.samples().cloned(), zero, n)
+        .map(|x| Complex::new(x, zero))
+        .collect();
+    complex::fft_in_place(y.as_mut_slice());
+    y
+}
 
 /// Compute the inverse cooley-tukey fast fourier transform of the complex 
 /// input collection and returns the real valued output collection
@@ -57,14 +65,57 @@ where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
-    I: IndexMut<usize, Output = Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    ifft_norm(x, Normalization::Backward)
+}
+
+/// Compute the inverse cooley-tukey fast fourier transform of the complex 
+/// input collection with the given normalization and returns the real valued
+/// output collection
+pub fn ifft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
     usize: AsPrimitive<F>,
 {   
-    let n = x.len();
-    complex::ifft_internal(x).iter().map(|x| x.re / n.as_()).collect()
+    let mut y = x.clone();
+    complex::ifft_in_place_norm(y.as_mut_slice(), norm);
+    y.iter().map(|x| x.re).collect()
 }
 
 
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length is not a power of two
+pub fn try_fft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length is not a power of two
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(ifft(x))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,4 +165,31 @@ mod tests {
     fn test_fft_ct_mix2_func_f32() {
         test_fft!(f32, Array1<f32>, Vec<Complex<f32>>, RTOL_F32, ATOL_F32);
     }
+
+    #[test]
+    fn test_fft_n_pads_and_truncates() {
+        let x: Vec<f64> = (0..100).map(|i| (i as f64 * 0.3).sin()).collect();
+        for n in [4, 64, 128] {
+            let output: Vec<Complex<f64>> = fft_n(&x, n);
+            let resized: Vec<f64> = x.iter().cloned().chain(core::iter::repeat(0.0)).take(n).collect();
+            let reference: Vec<Complex<f64>> = crate::fft::dft(&resized);
+            assert_eq!(output.len(), n);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_fft_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&empty), Err(RufftError::EmptyInput));
+        let x: Vec<f64> = vec![1.0; 12];
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&x), Err(RufftError::UnsupportedLength(12)));
+        let x: Vec<Complex<f64>> = vec![Complex::new(1.0, 0.0); 12];
+        assert_eq!(try_ifft::<f64, _, Vec<f64>>(&x), Err(RufftError::UnsupportedLength(12)));
+        let x: Vec<f64> = vec![1.0; 16];
+        assert!(try_fft::<f64, _, Vec<Complex<f64>>>(&x).is_ok());
+    }
 }
\ No newline at end of file
@@ -1,100 +1,337 @@
 use num_complex::Complex;
 use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
-use core::ops::IndexMut;
-use itertools::izip;
-use crate::traits::Iterable;
+use crate::error::RufftError;
+use crate::fft::{ resize, scale, Normalization };
+use crate::traits::{ Iterable, IterableMut };
 
-/// Computes the cooley-tukey fast fourier transform on the complex valued input
-/// collection and outputs a complex valued collection
-pub fn fft<F, I>(x: &I) -> I
+/// Checks that the length is supported by the radix-2 algorithm
+pub(crate) fn check_len(n: usize) -> Result<(), RufftError> {
+    if n == 0 {
+        Err(RufftError::EmptyInput)
+    } else if !n.is_power_of_two() {
+        Err(RufftError::UnsupportedLength(n))
+    } else {
+        Ok(())
+    }
+}
+
+/// Reorders the slice so that the item at index `i` is swapped with the item
+/// at the bit reversed index of `i`. The length of the slice must be a power 
+/// of two
+pub(crate) fn bit_reverse_permute<T>(x: &mut [T]) {
+    let n = x.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+}
+
+/// Iterative radix-2 kernel operating on the bit reversal permuted slice. The 
+/// sign of the twiddle factor exponent is `-1` for the forward transform and 
+/// `1` for the inverse transform. With the `std` feature the transforms use the
+/// precomputed twiddle factors instead, except for the allocation free
+/// transforms of `radix2_in_place`
+fn radix2<F>(x: &mut [Complex<F>], sign: F)
 where
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
-    I: IndexMut<usize, Output = Complex<F>>,
     usize: AsPrimitive<F>,
 {
     let n = x.len();
-    let n_f: F = n.as_();
-    let zero = Complex::new(F::zero(), F::zero());
-    let one_real = Complex::new(F::one(), F::zero());
-    let twopi_complex = Complex::new(F::zero(), F::TAU());
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let theta = sign * F::TAU() / len.as_();
+        for k in 0..half {
+            // Each twiddle factor is computed directly rather than by repeated
+            // multiplication to avoid accumulating rounding errors
+            let w = Complex::from_polar(F::one(), theta * k.as_());
+            for start in (0..n).step_by(len) {
+                let even = x[start + k];
+                let odd = x[start + k + half] * w;
+                x[start + k] = even + odd;
+                x[start + k + half] = even - odd;
+            }
+        }
+        len <<= 1;
+    }
+}
 
-    if n == 1 {
-        x.iter().cloned().collect()
-    } else {
-        let wn = -twopi_complex / n_f;
-        let wn = wn.exp();
-        let mut w = one_real;
+/// Computes the unnormalized radix-2 transform of the power of two slice
+/// in-place with the twiddle factors computed on the fly, so it never allocates
+/// regardless of the features. The sign is `-1` for the forward transform and 
+/// `1` for the inverse transform
+pub(crate) fn radix2_in_place<F>(x: &mut [Complex<F>], sign: F)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    bit_reverse_permute(x);
+    radix2(x, sign);
+}
 
-        let x_even: I = x.iter().step_by(2).cloned().collect();
-        let x_odd: I = x.iter().skip(1).step_by(2).cloned().collect();
+/// Computes the `n / 2` twiddle factors used by the radix-2 kernel for a
+/// transform of length `n`
+#[cfg(feature = "std")]
+pub(crate) fn twiddles<F>(n: usize) -> impl Iterator<Item = Complex<F>>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let theta = -F::TAU() / n.as_();
+    (0..n / 2).map(move |k| Complex::from_polar(F::one(), theta * k.as_()))
+}
 
-        let y_even: I = fft(&x_even);
-        let y_odd: I = fft(&x_odd);
-        let mut y = I::from_iter(core::iter::repeat(zero).take(n));
-        
-        izip!(y_even.iter(), y_odd.iter())
-            .enumerate()
-            .for_each(|(j, (even, odd))| {
-                let tmp = w * odd;
-                y[j] = even + tmp;
-                y[j + n / 2] = even - tmp;
-                w *= wn;
-            });
-        y
+/// Radix-2 butterflies of the stage combining transforms of length `len / 2`
+/// using the twiddle factors of the full length transform, which may be longer
+/// than the slice
+pub(crate) fn butterflies<F>(x: &mut [Complex<F>], twiddles: &[Complex<F>], len: usize)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+{
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    for chunk in x.chunks_exact_mut(len) {
+        let (lo, hi) = chunk.split_at_mut(half);
+        for (k, (even, odd)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+            let tmp = *odd * twiddles[k * stride];
+            *odd = *even - tmp;
+            *even += tmp;
+        }
     }
 }
 
-/// Internal inverse fourier transform implementation which returns a 
-/// complex valued collection
-pub(crate) fn ifft_internal<F, I>(x: &I) -> I
+/// Computes the butterflies of the stage, on x86_64 with the `std` feature the
+/// butterflies of `f32` and `f64` slices use the SIMD kernels supported by the
+/// cpu
+pub(crate) fn stage<F>(x: &mut [Complex<F>], twiddles: &[Complex<F>], len: usize)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+{
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    if crate::fft::ct::simd::butterflies(x, twiddles, len) {
+        return;
+    }
+    butterflies(x, twiddles, len);
+}
+
+/// Iterative radix-2 forward transform using the precomputed twiddle factors
+/// from `twiddles`. Used by plans which compute the twiddle factors only once.
+/// With the `rayon` feature the halves of long transforms are computed on 
+/// separate threads
+pub(crate) fn fft_with_twiddles<F>(x: &mut [Complex<F>], twiddles: &[Complex<F>])
 where
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
-    I: IndexMut<usize, Output = Complex<F>>,
-    usize: AsPrimitive<F>,
 {
     let n = x.len();
-    let zero = F::zero();
-    let one = F::one();
-    let twopi = F::TAU();
+    if n <= 1 {
+        return;
+    }
+    debug_assert_eq!(twiddles.len(), n / 2);
+    #[cfg(feature = "rayon")]
+    if crate::fft::parallel::fft_with_twiddles(x, twiddles) {
+        return;
+    }
+    bit_reverse_permute(x);
+    let mut len = 2;
+    while len <= n {
+        stage(x, twiddles, len);
+        len <<= 1;
+    }
+}
 
-    if n == 1 {
-        x.iter().cloned().collect()
-    } else {
-        let wn = Complex::new(zero, twopi / (n.as_()));
-        let wn = wn.exp();
-        let mut w = Complex::new(one, zero);
+/// Computes the forward transforms of both slices in-place, on separate 
+/// threads with the `rayon` feature
+pub(crate) fn fft_in_place_pair<F>(a: &mut [Complex<F>], b: &mut [Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    #[cfg(feature = "rayon")]
+    if crate::fft::parallel::fft_in_place_pair(a, b) {
+        return;
+    }
+    fft_in_place(a);
+    fft_in_place(b);
+}
 
-        let x_even: I = x.iter().step_by(2).cloned().collect();
-        let x_odd: I = x.iter().skip(1).step_by(2).cloned().collect();
+/// Computes the in-place iterative radix-2 cooley-tukey fast fourier transform
+/// of the complex valued slice. The length of the slice must be a power of two
+pub fn fft_in_place<F>(x: &mut [Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    fft_in_place_norm(x, Normalization::Backward);
+}
 
-        let y_even: I = ifft_internal(&x_even);
-        let y_odd: I = ifft_internal(&x_odd);
-        let mut y = I::from_iter(core::iter::repeat(Complex::new(zero, zero)).take(n));
+/// Computes the in-place iterative radix-2 cooley-tukey fast fourier transform
+/// of the complex valued slice with the given normalization. The length of the
+/// slice must be a power of two
+pub fn fft_in_place_norm<F>(x: &mut [Complex<F>], norm: Normalization)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    if n <= 1 {
+        return;
+    }
+    assert!(n.is_power_of_two(), "The radix-2 cooley-tukey fft requires a power of two length, got {}", n);
+    #[cfg(feature = "std")]
+    {
+        let twiddles: Vec<Complex<F>> = twiddles(n).collect();
+        fft_with_twiddles(x, &twiddles);
+    }
+    #[cfg(not(feature = "std"))]
+    radix2_in_place(x, -F::one());
+    scale(x, norm.forward_scale(n));
+}
 
-        izip!(y_even.iter(), y_odd.iter())
-            .enumerate()
-            .for_each(|(j, (even, odd))| {
-                let tmp = w * odd;
-                y[j] = even + tmp;
-                y[j + n / 2] = even - tmp;
-                w *= wn;
-            });
-        y
+/// Computes the in-place iterative radix-2 cooley-tukey inverse fast fourier 
+/// transform of the complex valued slice. The length of the slice must be a 
+/// power of two. 
+/// The output *is* normalized
+pub fn ifft_in_place<F>(x: &mut [Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    ifft_in_place_norm(x, Normalization::Backward);
+}
+
+/// Computes the in-place iterative radix-2 cooley-tukey inverse fast fourier 
+/// transform of the complex valued slice with the given normalization. The 
+/// length of the slice must be a power of two
+pub fn ifft_in_place_norm<F>(x: &mut [Complex<F>], norm: Normalization)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    if n <= 1 {
+        return;
     }
+    assert!(n.is_power_of_two(), "The radix-2 cooley-tukey ifft requires a power of two length, got {}", n);
+    #[cfg(feature = "std")]
+    {
+        // ifft(x) = conj(fft(conj(x)))
+        let twiddles: Vec<Complex<F>> = twiddles(n).collect();
+        x.iter_mut().for_each(|v| *v = v.conj());
+        fft_with_twiddles(x, &twiddles);
+        x.iter_mut().for_each(|v| *v = v.conj());
+    }
+    #[cfg(not(feature = "std"))]
+    radix2_in_place(x, F::one());
+    scale(x, norm.inverse_scale(n));
+}
+
+/// Computes the cooley-tukey fast fourier transform on the complex valued input
+/// collection and outputs a complex valued collection. The twiddle factors are
+/// computed on every call, use a `Plan` to reuse them across transforms
+pub fn fft<F, I>(x: &I) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    fft_norm(x, Normalization::Backward)
+}
+
+/// Computes the cooley-tukey fast fourier transform on the complex valued input
+/// collection with the given normalization and outputs a complex valued 
+/// collection
+pub fn fft_norm<F, I>(x: &I, norm: Normalization) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let mut y = x.clone();
+    fft_in_place_norm(y.as_mut_slice(), norm);
+    y
+}
+
+/// Computes the cooley-tukey fast fourier transform of length `n` on the 
+/// complex valued input collection, which is zero padded or truncated to length
+/// `n`, and outputs a complex valued collection. `n` must be a power of two
+pub fn fft_n<F, I>(x: &I, n: usize) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = Complex::new(F::zero(), F::zero());
+    let mut y: I = resize(x.iter().cloned(), zero, n).collect();
+    fft_in_place(y.as_mut_slice());
+    y
 }
 
+/// Computes the cooley-tukey inverse fast fourier transform on the complex 
+/// valued input collection and outputs a complex valued collection.
+/// The output *is* normalized
 pub fn ifft<F, I, C>(x: &I) -> C
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
-    I: IndexMut<usize, Output = Complex<F>>,
+    I: IterableMut,
     usize: AsPrimitive<F>,
 {   
-    let n: F = x.len().as_();
-    ifft_internal(x).iter().map(|x| x / n).collect()
+    ifft_norm(x, Normalization::Backward)
+}
+
+/// Computes the cooley-tukey inverse fast fourier transform on the complex 
+/// valued input collection with the given normalization and outputs a complex
+/// valued collection
+pub fn ifft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    let mut y = x.clone();
+    ifft_in_place_norm(y.as_mut_slice(), norm);
+    y.iter().cloned().collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length is not a power of two
+pub fn try_fft<F, I>(x: &I) -> Result<I, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length is not a power of two
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(ifft(x))
 }
 
 #[cfg(test)]
@@ -152,5 +389,48 @@ mod tests {
     fn test_ifft_arr_f32() {
         test_complex_ifft!(f32, Array1<Complex<f32>>, RTOL_F32, ATOL_F32);
     }
+
+    #[test]
+    fn test_fft_non_contiguous_arr_f64() {
+        let x: Vec<Complex<f64>> = (0..64).map(|i| Complex::new(i as f64, -(i as f64) / 2.0)).collect();
+        let mut arr: Array1<Complex<f64>> = x.iter().rev().cloned().collect();
+        arr.invert_axis(Axis(0)); // Same items as `x` but with a negative stride
+        let output = fft(&arr);
+        let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+        for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+            assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_fft_in_place_roundtrip_f64() {
+        let x: Vec<Complex<f64>> = (0..256).map(|i| Complex::new((i as f64).sin(), (i as f64).cos())).collect();
+        let mut y = x.clone();
+        fft_in_place(&mut y);
+        ifft_in_place(&mut y);
+        for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+            assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_fft_norm_matches_dft_norm_f64() {
+        let x: Vec<Complex<f64>> = (0..64).map(|i| Complex::new((i as f64).sin(), (i as f64).cos())).collect();
+        for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+            let output = fft_norm(&x, norm);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft_norm(&x, norm);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                    "{} => {} != {}", i, a, b);
+            }
+            let y: Vec<Complex<f64>> = ifft_norm(&output, norm);
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                    "{} => {} != {}", i, a, b);
+            }
+        }
+    }
     
 }
\ No newline at end of file
@@ -0,0 +1,339 @@
+//! SSE2, AVX and AVX2 with FMA radix-2 butterflies for `f32` and `f64` on
+//! x86_64. The instruction set is detected at runtime and the widest supported
+//! kernel is used, stages which are too short for the vector width and other
+//! float types fall back to the generic butterflies.
+//!
+//! The SSE2 and AVX kernels perform the same floating point operations in the
+//! same order as the generic butterflies so their results are identical, the
+//! FMA kernels round the fused complex products once and differ in the last
+//! bits.
+use core::any::TypeId;
+use core::arch::x86_64::*;
+use num_complex::Complex;
+
+/// Instruction sets of the butterfly kernels, ordered from narrowest to widest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum Level {
+    Sse2,
+    Avx,
+    Avx2Fma,
+}
+
+impl Level {
+    /// The widest instruction set supported by the cpu
+    pub(crate) fn detect() -> Option<Self> {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            Some(Self::Avx2Fma)
+        } else if is_x86_feature_detected!("avx") {
+            Some(Self::Avx)
+        } else if is_x86_feature_detected!("sse2") {
+            Some(Self::Sse2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Computes the radix-2 butterflies of the stage combining transforms of
+/// length `len / 2` with the twiddle factors of the full length transform.
+/// Returns false if the stage was not computed and the generic butterflies
+/// must be used instead
+pub(crate) fn butterflies<F: 'static>(x: &mut [Complex<F>], twiddles: &[Complex<F>], len: usize) -> bool {
+    match Level::detect() {
+        Some(level) => butterflies_with(level, x, twiddles, len),
+        None => false,
+    }
+}
+
+/// Computes the butterflies of the stage with the given instruction set, which
+/// must be supported by the cpu
+pub(crate) fn butterflies_with<F: 'static>(
+    level: Level,
+    x: &mut [Complex<F>],
+    twiddles: &[Complex<F>],
+    len: usize,
+) -> bool {
+    debug_assert!(Level::detect().is_some_and(|detected| level <= detected));
+    debug_assert_eq!(x.len() % len, 0);
+    let half = len / 2;
+    if TypeId::of::<F>() == TypeId::of::<f64>() {
+        // SAFETY: F is f64
+        let x = unsafe { &mut *(x as *mut [Complex<F>] as *mut [Complex<f64>]) };
+        let twiddles = unsafe { &*(twiddles as *const [Complex<F>] as *const [Complex<f64>]) };
+        // SAFETY: the instruction sets are supported by the cpu
+        unsafe {
+            match level {
+                Level::Avx2Fma if half >= 2 => stage_f64_fma(x, twiddles, len),
+                Level::Avx2Fma | Level::Avx if half >= 2 => stage_f64_avx(x, twiddles, len),
+                _ => stage_f64_sse2(x, twiddles, len),
+            }
+        }
+        true
+    } else if TypeId::of::<F>() == TypeId::of::<f32>() {
+        // SAFETY: F is f32
+        let x = unsafe { &mut *(x as *mut [Complex<F>] as *mut [Complex<f32>]) };
+        let twiddles = unsafe { &*(twiddles as *const [Complex<F>] as *const [Complex<f32>]) };
+        // SAFETY: the instruction sets are supported by the cpu
+        unsafe {
+            match level {
+                Level::Avx2Fma if half >= 4 => stage_f32_fma(x, twiddles, len),
+                Level::Avx2Fma | Level::Avx if half >= 4 => stage_f32_avx(x, twiddles, len),
+                _ if half >= 2 => stage_f32_sse2(x, twiddles, len),
+                _ => return false,
+            }
+        }
+        true
+    } else {
+        false
+    }
+}
+
+/// Complex product of one `f64` complex number with SSE2
+#[inline(always)]
+unsafe fn mul_f64_sse2(a: __m128d, w: __m128d) -> __m128d {
+    let wr = _mm_unpacklo_pd(w, w);
+    let wi = _mm_unpackhi_pd(w, w);
+    let swapped = _mm_shuffle_pd(a, a, 0b01);
+    // Negates the real part of the swapped product
+    let sign = _mm_set_pd(0.0, -0.0);
+    _mm_add_pd(_mm_mul_pd(a, wr), _mm_xor_pd(_mm_mul_pd(swapped, wi), sign))
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn stage_f64_sse2(x: &mut [Complex<f64>], twiddles: &[Complex<f64>], len: usize) {
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    let ptr = x.as_mut_ptr() as *mut f64;
+    for start in (0..x.len()).step_by(len) {
+        for k in 0..half {
+            let w = twiddles[k * stride];
+            let even = ptr.add(2 * (start + k));
+            let odd = ptr.add(2 * (start + k + half));
+            let a = _mm_loadu_pd(even);
+            let t = mul_f64_sse2(_mm_loadu_pd(odd), _mm_set_pd(w.im, w.re));
+            _mm_storeu_pd(even, _mm_add_pd(a, t));
+            _mm_storeu_pd(odd, _mm_sub_pd(a, t));
+        }
+    }
+}
+
+/// Loads the twiddle factors `k` and `k + 1` at the stride into a vector
+#[inline(always)]
+unsafe fn twiddles_f64(twiddles: &[Complex<f64>], k: usize, stride: usize) -> __m256d {
+    let w0 = twiddles[k * stride];
+    let w1 = twiddles[(k + 1) * stride];
+    _mm256_set_pd(w1.im, w1.re, w0.im, w0.re)
+}
+
+#[target_feature(enable = "avx")]
+unsafe fn stage_f64_avx(x: &mut [Complex<f64>], twiddles: &[Complex<f64>], len: usize) {
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    let ptr = x.as_mut_ptr() as *mut f64;
+    for start in (0..x.len()).step_by(len) {
+        for k in (0..half).step_by(2) {
+            let w = twiddles_f64(twiddles, k, stride);
+            let even = ptr.add(2 * (start + k));
+            let odd = ptr.add(2 * (start + k + half));
+            let a = _mm256_loadu_pd(even);
+            let b = _mm256_loadu_pd(odd);
+            let swapped = _mm256_permute_pd(b, 0b0101);
+            let t = _mm256_addsub_pd(
+                _mm256_mul_pd(b, _mm256_movedup_pd(w)),
+                _mm256_mul_pd(swapped, _mm256_permute_pd(w, 0b1111)),
+            );
+            _mm256_storeu_pd(even, _mm256_add_pd(a, t));
+            _mm256_storeu_pd(odd, _mm256_sub_pd(a, t));
+        }
+    }
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn stage_f64_fma(x: &mut [Complex<f64>], twiddles: &[Complex<f64>], len: usize) {
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    let ptr = x.as_mut_ptr() as *mut f64;
+    for start in (0..x.len()).step_by(len) {
+        for k in (0..half).step_by(2) {
+            let w = twiddles_f64(twiddles, k, stride);
+            let even = ptr.add(2 * (start + k));
+            let odd = ptr.add(2 * (start + k + half));
+            let a = _mm256_loadu_pd(even);
+            let b = _mm256_loadu_pd(odd);
+            let swapped = _mm256_permute_pd(b, 0b0101);
+            let t = _mm256_fmaddsub_pd(
+                b,
+                _mm256_movedup_pd(w),
+                _mm256_mul_pd(swapped, _mm256_permute_pd(w, 0b1111)),
+            );
+            _mm256_storeu_pd(even, _mm256_add_pd(a, t));
+            _mm256_storeu_pd(odd, _mm256_sub_pd(a, t));
+        }
+    }
+}
+
+#[target_feature(enable = "sse2")]
+unsafe fn stage_f32_sse2(x: &mut [Complex<f32>], twiddles: &[Complex<f32>], len: usize) {
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    let ptr = x.as_mut_ptr() as *mut f32;
+    // Negates the real parts of the swapped product
+    let sign = _mm_set_ps(0.0, -0.0, 0.0, -0.0);
+    for start in (0..x.len()).step_by(len) {
+        for k in (0..half).step_by(2) {
+            let w0 = twiddles[k * stride];
+            let w1 = twiddles[(k + 1) * stride];
+            let w = _mm_set_ps(w1.im, w1.re, w0.im, w0.re);
+            let even = ptr.add(2 * (start + k));
+            let odd = ptr.add(2 * (start + k + half));
+            let a = _mm_loadu_ps(even);
+            let b = _mm_loadu_ps(odd);
+            let wr = _mm_shuffle_ps(w, w, 0b10_10_00_00);
+            let wi = _mm_shuffle_ps(w, w, 0b11_11_01_01);
+            let swapped = _mm_shuffle_ps(b, b, 0b10_11_00_01);
+            let t = _mm_add_ps(_mm_mul_ps(b, wr), _mm_xor_ps(_mm_mul_ps(swapped, wi), sign));
+            _mm_storeu_ps(even, _mm_add_ps(a, t));
+            _mm_storeu_ps(odd, _mm_sub_ps(a, t));
+        }
+    }
+}
+
+/// Loads the twiddle factors `k` to `k + 3` at the stride into a vector
+#[inline(always)]
+unsafe fn twiddles_f32(twiddles: &[Complex<f32>], k: usize, stride: usize) -> __m256 {
+    let w0 = twiddles[k * stride];
+    let w1 = twiddles[(k + 1) * stride];
+    let w2 = twiddles[(k + 2) * stride];
+    let w3 = twiddles[(k + 3) * stride];
+    _mm256_set_ps(w3.im, w3.re, w2.im, w2.re, w1.im, w1.re, w0.im, w0.re)
+}
+
+#[target_feature(enable = "avx")]
+unsafe fn stage_f32_avx(x: &mut [Complex<f32>], twiddles: &[Complex<f32>], len: usize) {
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    let ptr = x.as_mut_ptr() as *mut f32;
+    for start in (0..x.len()).step_by(len) {
+        for k in (0..half).step_by(4) {
+            let w = twiddles_f32(twiddles, k, stride);
+            let even = ptr.add(2 * (start + k));
+            let odd = ptr.add(2 * (start + k + half));
+            let a = _mm256_loadu_ps(even);
+            let b = _mm256_loadu_ps(odd);
+            let swapped = _mm256_permute_ps(b, 0b10_11_00_01);
+            let t = _mm256_addsub_ps(
+                _mm256_mul_ps(b, _mm256_moveldup_ps(w)),
+                _mm256_mul_ps(swapped, _mm256_movehdup_ps(w)),
+            );
+            _mm256_storeu_ps(even, _mm256_add_ps(a, t));
+            _mm256_storeu_ps(odd, _mm256_sub_ps(a, t));
+        }
+    }
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn stage_f32_fma(x: &mut [Complex<f32>], twiddles: &[Complex<f32>], len: usize) {
+    let half = len / 2;
+    let stride = 2 * twiddles.len() / len;
+    let ptr = x.as_mut_ptr() as *mut f32;
+    for start in (0..x.len()).step_by(len) {
+        for k in (0..half).step_by(4) {
+            let w = twiddles_f32(twiddles, k, stride);
+            let even = ptr.add(2 * (start + k));
+            let odd = ptr.add(2 * (start + k + half));
+            let a = _mm256_loadu_ps(even);
+            let b = _mm256_loadu_ps(odd);
+            let swapped = _mm256_permute_ps(b, 0b10_11_00_01);
+            let t = _mm256_fmaddsub_ps(
+                b,
+                _mm256_moveldup_ps(w),
+                _mm256_mul_ps(swapped, _mm256_movehdup_ps(w)),
+            );
+            _mm256_storeu_ps(even, _mm256_add_ps(a, t));
+            _mm256_storeu_ps(odd, _mm256_sub_ps(a, t));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::ct::complex::{ bit_reverse_permute, butterflies as generic, twiddles };
+    use crate::test_utils as test;
+    use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+
+    /// Computes the transform with the given kernels, or the generic
+    /// butterflies if `level` is `None`
+    fn transform<F>(x: &[Complex<F>], level: Option<Level>) -> Vec<Complex<F>>
+    where
+        F: Float + FloatConst + NumAssign + 'static,
+        usize: AsPrimitive<F>,
+    {
+        let n = x.len();
+        let twiddles: Vec<Complex<F>> = twiddles(n).collect();
+        let mut y = x.to_vec();
+        bit_reverse_permute(&mut y);
+        let mut len = 2;
+        while len <= n {
+            let done = level.is_some_and(|level| butterflies_with(level, &mut y, &twiddles, len));
+            if !done {
+                generic(&mut y, &twiddles, len);
+            }
+            len <<= 1;
+        }
+        y
+    }
+
+    fn levels() -> Vec<Level> {
+        let detected = Level::detect();
+        [Level::Sse2, Level::Avx, Level::Avx2Fma]
+            .into_iter()
+            .filter(|&level| detected.is_some_and(|detected| level <= detected))
+            .collect()
+    }
+
+    #[test]
+    fn test_butterflies_f64() {
+        for level in levels() {
+            for n in [1, 2, 4, 8, 64, 1024, 4096] {
+                let x = test::signal::<f64>(n);
+                let expected = transform(&x, None);
+                let y = transform(&x, Some(level));
+                for (i, (a, b)) in y.iter().zip(expected.iter()).enumerate() {
+                    if level == Level::Avx2Fma {
+                        assert!(test::nearly_equal_complex(*a, *b, 1e-12, 1e-12), "{:?} n = {}, {} => {} != {}", level, n, i, a, b);
+                    } else {
+                        assert_eq!(a, b, "{:?} n = {}, {}", level, n, i);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_butterflies_f32() {
+        for level in levels() {
+            for n in [1, 2, 4, 8, 64, 1024, 4096] {
+                let x = test::signal::<f32>(n);
+                let expected = transform(&x, None);
+                let y = transform(&x, Some(level));
+                for (i, (a, b)) in y.iter().zip(expected.iter()).enumerate() {
+                    if level == Level::Avx2Fma {
+                        assert!(test::nearly_equal_complex(*a, *b, 1e-4, 1e-3), "{:?} n = {}, {} => {} != {}", level, n, i, a, b);
+                    } else {
+                        assert_eq!(a, b, "{:?} n = {}, {}", level, n, i);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_other_types_fall_back() {
+        let mut x: Vec<Complex<i32>> = vec![Complex::new(1, 0); 8];
+        let twiddles = vec![Complex::new(1, 0); 4];
+        if let Some(level) = Level::detect() {
+            assert!(!butterflies_with(level, &mut x, &twiddles, 8));
+        }
+    }
+}
@@ -1,84 +1,266 @@
+pub mod complex;
 use num_complex::Complex;
 use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
-use core::ops::IndexMut;
-use super::ct;
-use crate::itertools::complex::zero_pad;
-use crate::traits::Iterable;
+use super::{ ct, resize, Normalization };
+use crate::error::RufftError;
+use crate::traits::{ Iterable, IterableMut, Signal };
 
-fn chirp_complex<F, I>(n: usize) -> I
+/// Bluestein chirp `exp(-iπk²/n)` for `k` in `0..n`. The exponent is reduced 
+/// modulo `2n` before converting to floating point which preserves precision
+/// for large `k`
+pub(crate) fn bluestein_chirp<F>(n: usize) -> impl Iterator<Item = Complex<F>>
 where 
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     usize: AsPrimitive<F>
 {
-    (0..n).map(|i| {
-        Complex::from_polar(F::one(), 
-        (F::PI() * i.as_() * i.as_()) / n.as_())
-    }).collect()
+    let two_n = 2 * n as u128;
+    (0..n).map(move |k| {
+        let k2 = ((k as u128 * k as u128) % two_n) as usize;
+        Complex::from_polar(F::one(), -F::PI() * k2.as_() / n.as_())
+    })
 }
 
-fn inverse_chirp_complex<I, F>(n: usize) -> I
+/// Allocation free Bluestein's algorithm computing the forward transform of `x`
+/// in-place. The scratch space must hold at least two power of two transforms
+/// of length `(2n - 1).next_power_of_two()`
+pub(crate) fn bluestein_in_place<F>(x: &mut [Complex<F>], scratch: &mut [Complex<F>])
+where 
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{
+    let n = x.len();
+    let zero = Complex::new(F::zero(), F::zero());
+    let fft_len = (2 * n - 1).next_power_of_two();
+    let (a, b) = scratch[..2 * fft_len].split_at_mut(fft_len);
+
+    a.iter_mut().for_each(|v| *v = zero);
+    a.iter_mut()
+        .zip(x.iter().zip(bluestein_chirp::<F>(n)))
+        .for_each(|(a, (v, c))| *a = v * c);
+    // The conjugate chirp is reflected about zero so that the circular 
+    // convolution computes the linear convolution of the first n items
+    b.iter_mut().for_each(|v| *v = zero);
+    for (k, c) in bluestein_chirp::<F>(n).enumerate() {
+        b[k] = c.conj();
+        if k > 0 {
+            b[fft_len - k] = c.conj();
+        }
+    }
+
+    ct::complex::radix2_in_place(a, -F::one());
+    ct::complex::radix2_in_place(b, -F::one());
+    a.iter_mut()
+        .zip(b.iter())
+        .for_each(|(a, b)| *a *= b);
+    // The unnormalized inverse transform is scaled with the final chirp
+    ct::complex::radix2_in_place(a, F::one());
+
+    let scale: F = F::one() / fft_len.as_();
+    x.iter_mut()
+        .zip(a.iter().zip(bluestein_chirp::<F>(n)))
+        .for_each(|(v, (a, c))| *v = a * c.scale(scale));
+}
+
+/// Precomputed state for Bluestein's algorithm which expresses a length `n` 
+/// DFT as a convolution computed with power of two radix-2 transforms
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub(crate) struct Bluestein<F> {
+    chirp: Vec<Complex<F>>,
+    chirp_fft: Vec<Complex<F>>,
+    twiddles: Vec<Complex<F>>,
+    scratch: Vec<Complex<F>>,
+}
+
+#[cfg(feature = "std")]
+impl<F> Bluestein<F>
 where 
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
     usize: AsPrimitive<F>
 {
-    (0..n).map(|i| {
-        Complex::from_polar(F::one(), 
-        (-F::one() * F::PI() * i.as_() * i.as_()) / n.as_())
-    }).collect()
+    pub(crate) fn new(n: usize) -> Self {
+        let fft_len = (2 * n - 1).next_power_of_two();
+        let chirp: Vec<Complex<F>> = bluestein_chirp(n).collect();
+        let twiddles: Vec<Complex<F>> = ct::complex::twiddles(fft_len).collect();
+
+        let zero = Complex::new(F::zero(), F::zero());
+        let mut chirp_fft = vec![zero; fft_len];
+        chirp_fft[0] = chirp[0].conj();
+        for k in 1..n {
+            chirp_fft[k] = chirp[k].conj();
+            chirp_fft[fft_len - k] = chirp[k].conj();
+        }
+        ct::complex::fft_with_twiddles(&mut chirp_fft, &twiddles);
+        // Fold the normalization of the inverse transform into the chirp spectrum
+        let scale: F = fft_len.as_();
+        chirp_fft.iter_mut().for_each(|c| *c /= scale);
+
+        Self { chirp, chirp_fft, twiddles, scratch: vec![zero; fft_len] }
+    }
+
+    pub(crate) fn process(&mut self, x: &mut [Complex<F>]) {
+        let zero = Complex::new(F::zero(), F::zero());
+        let n = x.len();
+        self.scratch.iter_mut().for_each(|s| *s = zero);
+        self.scratch[..n].iter_mut()
+            .zip(x.iter().zip(self.chirp.iter()))
+            .for_each(|(s, (v, c))| *s = v * c);
+
+        ct::complex::fft_with_twiddles(&mut self.scratch, &self.twiddles);
+        // The inverse transform is computed as conj(fft(conj(x)))
+        self.scratch.iter_mut()
+            .zip(self.chirp_fft.iter())
+            .for_each(|(s, c)| *s = (*s * c).conj());
+        ct::complex::fft_with_twiddles(&mut self.scratch, &self.twiddles);
+
+        x.iter_mut()
+            .zip(self.scratch.iter().zip(self.chirp.iter()))
+            .for_each(|(v, (s, c))| *v = s.conj() * c);
+    }
 }
 
 /// Computes the chirp-z fast fourier transform of the real valued 
-/// input collection
+/// input collection. As with `complex::fft` the chirps are computed per call
 pub fn fft<F, I, C>(x: &I) -> C
 where
     F: Float + FloatConst + NumAssign + 'static,
-    for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: Signal<Sample = F> + ?Sized,
     for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
-    for<'c><C as Iterable>::Iterator<'c>: DoubleEndedIterator,
-    C: IndexMut<usize, Output = Complex<F>>,
+    C: IterableMut,
     usize: AsPrimitive<F>,
-    
 {
-    let n = x.len();
-    let m = (2 * n) -1;
-    let fft_len = m.next_power_of_two(); // Just use cooley-tukey for now
-    let zero_pad_len = fft_len - m + n;
-
-    let a: C = inverse_chirp_complex::<C, F>(n)
-        .iter()
-        .zip(x.iter())
-        .map(|(c, v)| c * v)
+    fft_norm(x, Normalization::Backward)
+}
+
+/// Computes the chirp-z fast fourier transform of the real valued 
+/// input collection with the given normalization
+pub fn fft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let y: C = x.samples()
+        .map(|&x| Complex::new(x, zero))
         .collect();
-    let b: C = chirp_complex(n);
-    let reflection: C = b.iter().skip(1).take(n - 1).rev().cloned().collect();
+    complex::fft_norm(&y, norm)
+}
 
-    let a = zero_pad(fft_len, &a).expect("Internal padding error which should be impossible !");
-    let b = zero_pad(zero_pad_len, &b).expect("Internal padding error which should be impossible !");
+/// Computes the chirp-z fast fourier transform of length `n` of the real 
+/// valued input collection, which is zero padded or truncated to length `n`.
+/// Equivalent to numpy's `fft(x, n)`
+pub fn fft_n<F, I, C>(x: &I, n: usize) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let y = resize(x.samples().cloned(), zero, n).map(|x| Complex::new(x, zero));
+    complex::bluestein(y, n, Normalization::Backward)
+}
 
-    let b: C = b.iter().chain(reflection.iter()).cloned().collect();
+/// Computes the inverse chirp-z fast fourier transform of the complex input 
+/// collection and returns the real valued output collection
+/// The output *is* normalized.
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    ifft_norm(x, Normalization::Backward)
+}
 
-    let afft = ct::complex::fft(&a);
-    let bfft = ct::complex::fft(&b);
-    let convolution: C = afft
-        .iter()
-        .zip(bfft.iter())
-        .map(|(a, b)| a * b)
+/// Computes the inverse chirp-z fast fourier transform of the complex input 
+/// collection with the given normalization and returns the real valued output
+/// collection
+pub fn ifft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    let y: I = complex::ifft_norm(x, norm);
+    y.iter().map(|x| x.re).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty
+pub fn try_fft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+/// Computes the chirp-z transform of the real valued input collection, the 
+/// z-transform evaluated at the `m` points `a * w^-k` for `k` in `0..m` along 
+/// the spiral contour defined by `w` and `a`
+pub fn czt<F, I, C>(x: &I, m: usize, w: Complex<F>, a: Complex<F>) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let y: C = x.samples()
+        .map(|&x| Complex::new(x, zero))
         .collect();
+    complex::czt(&y, m, w, a)
+}
 
-    let tmp: C = ct::complex::ifft(&convolution);
-    let product: C = inverse_chirp_complex(n);
-    tmp.iter()
-        .zip(product.iter())
-        .map(|(a, b)| a * b)
-        .collect()  
+/// Computes the `m` point zoom fft of the real valued input collection over
+/// the frequency band from `f_start` up to, but not including, `f_end` where 
+/// `fs` is the sampling frequency. This gives a high resolution spectrum of a
+/// narrow band without computing a large zero padded fft
+pub fn zoom_fft<F, I, C>(x: &I, f_start: F, f_end: F, m: usize, fs: F) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let y: C = x.samples()
+        .map(|&x| Complex::new(x, zero))
+        .collect();
+    complex::zoom_fft(&y, f_start, f_end, m, fs)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::test_fft;
+    use crate::test_utils::{ test_fft, test_ifft };
     use ndarray::prelude::*;
 
     const ATOL_F64: f64 = 1e-12;
@@ -124,4 +306,69 @@ mod tests {
     fn test_fft_ct_mix2_func_f32() {
         test_fft!(f32, Array1<f32>, Vec<Complex<f32>>, RTOL_F32, ATOL_F32);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ifft_czt_vec_f64() {
+        test_ifft!(f64, Vec<Complex<f64>>, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+    #[test]
+    fn test_ifft_czt_arr_f64() {
+        test_ifft!(f64, Array1<Complex<f64>>, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+    #[test]
+    fn test_ifft_czt_vec_f32() {
+        test_ifft!(f32, Vec<Complex<f32>>, Vec<f32>, RTOL_F32, ATOL_F32);
+    }
+
+    #[test]
+    fn test_ifft_czt_roundtrip_non_power_of_two() {
+        let x: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.3).sin()).collect();
+        let spectrum: Vec<Complex<f64>> = fft(&x);
+        let y: Vec<f64> = ifft(&spectrum);
+        for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+            assert!(crate::test_utils::nearly_equal(a, b, RTOL_F64, 1e-10), 
+                "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_zoom_fft_matches_dtft() {
+        let fs = 100.0;
+        let x: Vec<f64> = (0..200).map(|i| (core::f64::consts::TAU * 10.3 * i as f64 / fs).sin()).collect();
+        let (f_start, f_end, m) = (9.0, 12.0, 64);
+        let output: Vec<Complex<f64>> = zoom_fft(&x, f_start, f_end, m, fs);
+        let omega: Vec<f64> = (0..m)
+            .map(|k| core::f64::consts::TAU * (f_start + k as f64 * (f_end - f_start) / m as f64) / fs)
+            .collect();
+        let reference: Vec<Complex<f64>> = crate::fft::dtft::<f64, Vec<f64>, Vec<Complex<f64>>>(x)(omega);
+        for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+            assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-9), 
+                "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_fft_n_pads_and_truncates() {
+        let x: Vec<f64> = (0..100).map(|i| (i as f64 * 0.3).sin()).collect();
+        for n in [5, 64, 90, 150] {
+            let output: Vec<Complex<f64>> = fft_n(&x, n);
+            let resized: Vec<f64> = x.iter().cloned().chain(core::iter::repeat(0.0)).take(n).collect();
+            let reference: Vec<Complex<f64>> = crate::fft::dft(&resized);
+            assert_eq!(output.len(), n);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let empty: Vec<f64> = vec![];
+        let output: Vec<Complex<f64>> = fft(&empty);
+        assert!(output.is_empty());
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&empty), Err(RufftError::EmptyInput));
+        let empty: Vec<Complex<f64>> = vec![];
+        assert_eq!(try_ifft::<f64, _, Vec<f64>>(&empty), Err(RufftError::EmptyInput));
+    }
+}
@@ -0,0 +1,345 @@
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use super::{ bluestein_chirp, ct };
+use crate::error::RufftError;
+use crate::fft::{ resize, Normalization };
+use crate::traits::{ Iterable, IterableMut, Signal };
+
+/// Computes the chirp-z fast fourier transform on the complex valued input
+/// collection and outputs a complex valued collection. The transform is 
+/// computed with Bluestein's algorithm as a convolution of power of two length.
+/// The chirps and the spectrum of the convolution kernel are rebuilt on every
+/// call, a `Plan` precomputes them once for repeated transforms of one length
+pub fn fft<F, I>(x: &I) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    fft_norm(x, Normalization::Backward)
+}
+
+/// Computes the chirp-z fast fourier transform on the complex valued input
+/// collection with the given normalization and outputs a complex valued 
+/// collection
+pub fn fft_norm<F, I>(x: &I, norm: Normalization) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    bluestein(x.iter().cloned(), x.len(), norm)
+}
+
+/// Computes the chirp-z fast fourier transform of length `n` on the complex
+/// valued input collection, which is zero padded or truncated to length `n`,
+/// and outputs a complex valued collection. Equivalent to numpy's `fft(x, n)`
+pub fn fft_n<F, I>(x: &I, n: usize) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = Complex::new(F::zero(), F::zero());
+    bluestein(resize(x.iter().cloned(), zero, n), n, Normalization::Backward)
+}
+
+/// Checks that the input is not empty, the chirp-z transform supports any 
+/// other length
+pub(crate) fn check_len(n: usize) -> Result<(), RufftError> {
+    if n == 0 {
+        Err(RufftError::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+/// Bluestein's algorithm applied to the `n` items of the iterator
+pub(crate) fn bluestein<F, I>(x: impl Iterator<Item = Complex<F>>, n: usize, norm: Normalization) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    if n == 0 {
+        return I::from_iter(core::iter::empty());
+    }
+    let zero = Complex::new(F::zero(), F::zero());
+    let fft_len = (2 * n - 1).next_power_of_two(); // Just use cooley-tukey for now
+
+    let chirp: I = bluestein_chirp(n).collect();
+    let mut a: I = x
+        .zip(chirp.iter())
+        .map(|(v, c)| v * c)
+        .chain(core::iter::repeat(zero))
+        .take(fft_len)
+        .collect();
+    // The conjugate chirp is reflected about zero so that the circular 
+    // convolution computes the linear convolution of the first n items
+    let mut b: I = chirp.iter()
+        .map(|c| c.conj())
+        .chain(core::iter::repeat_n(zero, fft_len - (2 * n - 1)))
+        .chain(chirp.iter().skip(1).rev().map(|c| c.conj()))
+        .collect();
+
+    let a_slice = a.as_mut_slice();
+    let b_slice = b.as_mut_slice();
+    ct::complex::fft_in_place_pair(a_slice, b_slice);
+    a_slice.iter_mut()
+        .zip(b_slice.iter())
+        .for_each(|(a, b)| *a *= b);
+    ct::complex::ifft_in_place(a_slice);
+
+    let scale: F = norm.forward_scale(n);
+    a_slice.iter()
+        .zip(chirp.iter())
+        .map(|(a, c)| a * c * scale)
+        .collect()
+}
+
+/// Computes the inverse chirp-z fast fourier transform on the complex valued 
+/// input collection and outputs a complex valued collection.
+/// The output *is* normalized
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    ifft_norm(x, Normalization::Backward)
+}
+
+/// Computes the inverse chirp-z fast fourier transform on the complex valued 
+/// input collection with the given normalization and outputs a complex valued
+/// collection
+pub fn ifft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    // The inverse transform is computed as conj(fft(conj(x))) scaled by the
+    // inverse normalization
+    let conj: I = x.iter().map(|v| v.conj()).collect();
+    fft_norm(&conj, norm.swap()).iter().map(|v| v.conj()).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty
+pub fn try_fft<F, I>(x: &I) -> Result<I, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+/// Computes the chirp-z transform of the complex valued input collection, the 
+/// z-transform evaluated at the `m` points `a * w^-k` for `k` in `0..m` along 
+/// the spiral contour defined by `w` and `a`. The DFT is the special case where
+/// `m` is the length of the input, `w = exp(-2πi/n)` and `a = 1`
+pub fn czt<F, I, C>(x: &I, m: usize, w: Complex<F>, a: Complex<F>) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = Complex<F>> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let zero = Complex::new(F::zero(), F::zero());
+    if n == 0 || m == 0 {
+        return core::iter::repeat_n(zero, m).collect();
+    }
+    let fft_len = (n + m - 1).next_power_of_two();
+    let half: F = F::one() / (F::one() + F::one());
+    // w^(k²/2) computed from the principal logarithm so that the chirps are
+    // consistent with each other for any w
+    let chirp = |k: usize| -> Complex<F> {
+        let k: F = k.as_();
+        w.powf(k * k * half)
+    };
+    let a_inv = a.inv();
+
+    let mut y: C = x.samples()
+        .enumerate()
+        .map(|(j, v)| v * a_inv.powu(j as u32) * chirp(j))
+        .chain(core::iter::repeat(zero))
+        .take(fft_len)
+        .collect();
+    let mut v: C = (0..fft_len).map(|k| {
+        if k < m {
+            chirp(k).inv()
+        } else if k > fft_len - n {
+            chirp(fft_len - k).inv()
+        } else {
+            zero
+        }
+    }).collect();
+
+    let y_slice = y.as_mut_slice();
+    let v_slice = v.as_mut_slice();
+    ct::complex::fft_in_place(y_slice);
+    ct::complex::fft_in_place(v_slice);
+    y_slice.iter_mut()
+        .zip(v_slice.iter())
+        .for_each(|(y, v)| *y *= v);
+    ct::complex::ifft_in_place(y_slice);
+
+    y_slice.iter()
+        .take(m)
+        .enumerate()
+        .map(|(k, g)| g * chirp(k))
+        .collect()
+}
+
+/// Computes the `m` point zoom fft of the complex valued input collection over
+/// the frequency band from `f_start` up to, but not including, `f_end` where 
+/// `fs` is the sampling frequency. The frequency of bin `k` is 
+/// `f_start + k * (f_end - f_start) / m`
+pub fn zoom_fft<F, I, C>(x: &I, f_start: F, f_end: F, m: usize, fs: F) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = Complex<F>> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let step = (f_end - f_start) / m.as_();
+    let w = Complex::from_polar(F::one(), -F::TAU() * step / fs);
+    let a = Complex::from_polar(F::one(), F::TAU() * f_start / fs);
+    czt(x, m, w, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ test_complex_fft, test_complex_ifft };
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-12;
+    const RTOL_F64: f64 = 1e-9;
+
+    // Really loose tolerances for f32 because we're checking complex numbers
+    // which is more difficult, especially near zero where the phase can suddenly
+    // jump by π for a small change in the real or imaginary part. Precision errors
+    // for FFT algorithms can also accumulate. These values were found by trial-and-error.
+    const ATOL_F32: f32 = 1e-1;
+    const RTOL_F32: f32 = 1e-1;
+
+    #[test]
+    fn test_fft_czt_vec_func_f64() {
+        test_complex_fft!(f64, Vec<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_czt_arr_func_f64() {
+        test_complex_fft!(f64, Array1<Complex<f64>>,  RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_czt_vec_func_f32() {
+        test_complex_fft!(f32, Vec<Complex<f32>>, RTOL_F32, ATOL_F32);
+    }
+
+    #[test]
+    fn test_ifft_czt_vec_f64() {
+        test_complex_ifft!(f64, Vec<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_ifft_czt_arr_f64() {
+        test_complex_ifft!(f64, Array1<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_czt_matches_dft() {
+        for n in [1, 2, 3, 10, 121, 1000] {
+            let x: Vec<Complex<f64>> = (0..n)
+                .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+                .collect();
+            let output = fft(&x);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_czt_unit_circle_matches_dft() {
+        let n = 30;
+        let x: Vec<Complex<f64>> = (0..n)
+            .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+            .collect();
+        let w = Complex::from_polar(1.0, -core::f64::consts::TAU / n as f64);
+        let output: Vec<Complex<f64>> = czt(&x, n, w, Complex::new(1.0, 0.0));
+        let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+        for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+            assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_czt_spiral_contour() {
+        // Points off the unit circle are checked against the direct evaluation
+        // of the z-transform
+        let x: Vec<Complex<f64>> = (0..20)
+            .map(|i| Complex::new(1.0 / (i as f64 + 1.0), (i as f64 * 0.2).sin()))
+            .collect();
+        let (m, w, a) = (45, Complex::from_polar(0.995, -0.05), Complex::from_polar(0.9, 0.3));
+        let output: Vec<Complex<f64>> = czt(&x, m, w, a);
+        for (k, &out) in output.iter().enumerate() {
+            let z = a * w.powi(-(k as i32));
+            let reference: Complex<f64> = x.iter()
+                .enumerate()
+                .map(|(j, v)| v * z.powi(-(j as i32)))
+                .sum();
+            assert!(crate::test_utils::nearly_equal_complex(out, reference, RTOL_F64, 1e-10), 
+                "{} => {} != {}", k, out, reference);
+        }
+    }
+
+    #[test]
+    fn test_fft_norm_matches_dft_norm_f64() {
+        let x: Vec<Complex<f64>> = (0..37).map(|i| Complex::new((i as f64).sin(), (i as f64).cos())).collect();
+        for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+            let output = fft_norm(&x, norm);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft_norm(&x, norm);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "{} => {} != {}", i, a, b);
+            }
+            let y: Vec<Complex<f64>> = ifft_norm(&output, norm);
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "{} => {} != {}", i, a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Discrete cosine transforms of types I to IV, computed in `O(n log n)` with
+//! the crate's complex ffts. The unnormalized definitions match scipy's `dct`
+//! function e.g. the type II transform is
+//!
+//! `y[k] = 2 Σ x[n] cos(πk(2n + 1) / 2N)`
+//!
+//! and the `norm` argument has the same meaning as scipy's `norm` argument,
+//! with `Normalization::Ortho` giving orthonormal transforms.
+//!
+//! ```
+//! use rufft::fft::{ Normalization, dct };
+//!
+//! let x = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+//! let y: Vec<f64> = dct::dct(&x, dct::Type::II, Normalization::Ortho);
+//! let _: Vec<f64> = dct::idct(&y, dct::Type::II, Normalization::Ortho);
+//! ```
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::Normalization;
+use crate::fft::plan::Plan;
+use crate::fft::real;
+use crate::traits::{ Iterable, Signal };
+
+/// The type of a discrete cosine or sine transform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Type {
+    I,
+    II,
+    III,
+    IV,
+}
+
+impl Type {
+    /// The type of the inverse transform. Types II and III are each other's
+    /// inverse while types I and IV are their own inverse
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::I => Self::I,
+            Self::II => Self::III,
+            Self::III => Self::II,
+            Self::IV => Self::IV,
+        }
+    }
+}
+
+/// Computes `exp(iθ)`
+fn expi<F: Float>(theta: F) -> Complex<F> {
+    Complex::from_polar(F::one(), theta)
+}
+
+/// Unnormalized type I transform computed from the real fft of the even 
+/// symmetric extension of `x` of length `2(n - 1)`
+fn dct1<F>(x: &[F]) -> Vec<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let extended: Vec<F> = x.iter()
+        .chain(x[1..n - 1].iter().rev())
+        .cloned()
+        .collect();
+    let spectrum: Vec<Complex<F>> = real::rfft(&extended);
+    spectrum.iter().take(n).map(|v| v.re).collect()
+}
+
+/// Unnormalized type II transform computed with Makhoul's algorithm, a length
+/// `n` complex fft of the reordered input
+pub(crate) fn dct2<F>(x: &[F]) -> Vec<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let two = F::one() + F::one();
+    // The even indexed items in order followed by the odd indexed items reversed
+    let mut v: Vec<Complex<F>> = x.iter()
+        .step_by(2)
+        .chain(x.iter().skip(1).step_by(2).rev())
+        .map(|&v| Complex::new(v, F::zero()))
+        .collect();
+    Plan::forward(n).process(&mut v);
+    let theta = -F::PI() / (two * n.as_());
+    v.iter()
+        .enumerate()
+        .map(|(k, v)| two * (v * expi(theta * k.as_())).re)
+        .collect()
+}
+
+/// Unnormalized type III transform computed with the inverse of Makhoul's 
+/// algorithm
+pub(crate) fn dct3<F>(x: &[F]) -> Vec<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let two = F::one() + F::one();
+    let theta = F::PI() / (two * n.as_());
+    let mut z: Vec<Complex<F>> = (0..n)
+        .map(|k| {
+            let reflected = if k == 0 { F::zero() } else { x[n - k] };
+            Complex::new(x[k], -reflected) * expi(theta * k.as_())
+        })
+        .collect();
+    // Unnormalized inverse transform
+    Plan::inverse(n).process(&mut z);
+    let scale: F = n.as_();
+    let mut y = vec![F::zero(); n];
+    for (m, v) in z.iter().enumerate() {
+        let index = if 2 * m < n { 2 * m } else { 2 * (n - 1 - m) + 1 };
+        y[index] = v.re * scale;
+    }
+    y
+}
+
+/// Unnormalized type IV transform computed from a length `2n` complex fft of 
+/// the pre-twiddled input
+pub(crate) fn dct4<F>(x: &[F]) -> Vec<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let two = F::one() + F::one();
+    let theta = -F::PI() / (two * n.as_());
+    let mut z: Vec<Complex<F>> = x.iter()
+        .enumerate()
+        .map(|(j, &v)| expi(theta * j.as_()) * v)
+        .chain(core::iter::repeat_n(Complex::new(F::zero(), F::zero()), n))
+        .collect();
+    Plan::forward(2 * n).process(&mut z);
+    z.iter()
+        .take(n)
+        .enumerate()
+        .map(|(k, v)| two * (v * expi(theta * (two * k.as_() + F::one()) / two)).re)
+        .collect()
+}
+
+/// Computes the discrete cosine transform of the given type of the real valued
+/// input collection. Type I transforms require at least two items
+pub fn dct<F, I, C>(x: &I, dct_type: Type, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    let mut x: Vec<F> = x.samples().cloned().collect();
+    let n = x.len();
+    if n == 0 {
+        return C::from_iter(core::iter::empty());
+    }
+    let two = F::one() + F::one();
+    let sqrt2 = two.sqrt();
+    let ortho = norm == Normalization::Ortho;
+
+    let y = match dct_type {
+        Type::I => {
+            assert!(n > 1, "The type I discrete cosine transform requires at least two items");
+            if ortho {
+                x[0] *= sqrt2;
+                x[n - 1] *= sqrt2;
+            }
+            let mut y = dct1(&x);
+            if ortho {
+                y[0] /= sqrt2;
+                y[n - 1] /= sqrt2;
+            }
+            y
+        },
+        Type::II => {
+            let mut y = dct2(&x);
+            if ortho {
+                y[0] /= sqrt2;
+            }
+            y
+        },
+        Type::III => {
+            if ortho {
+                x[0] *= sqrt2;
+            }
+            dct3(&x)
+        },
+        Type::IV => dct4(&x),
+    };
+
+    let n_logical: F = match dct_type {
+        Type::I => (n - 1).as_(),
+        _ => n.as_(),
+    };
+    let scale = match norm {
+        Normalization::Backward => F::one(),
+        Normalization::Ortho => F::one() / (two * n_logical).sqrt(),
+        Normalization::Forward => F::one() / (two * n_logical),
+    };
+    y.into_iter().map(|v| v * scale).collect()
+}
+
+/// Computes the inverse discrete cosine transform of the given type of the 
+/// real valued input collection, where `idct(dct(x, t, norm), t, norm) == x`
+pub fn idct<F, I, C>(x: &I, dct_type: Type, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    dct(x, dct_type.inverse(), norm.swap())
+}
+
+/// Checks that the transform of the given type supports the length
+fn check_len(n: usize, dct_type: Type) -> Result<(), RufftError> {
+    if n == 0 {
+        Err(RufftError::EmptyInput)
+    } else if dct_type == Type::I && n == 1 {
+        Err(RufftError::UnsupportedLength(n))
+    } else {
+        Ok(())
+    }
+}
+
+/// Fallible version of `dct`, returns an error if the collection is empty or
+/// has a single item for a type I transform
+pub fn try_dct<F, I, C>(x: &I, dct_type: Type, norm: Normalization) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len(), dct_type)?;
+    Ok(dct(x, dct_type, norm))
+}
+
+/// Fallible version of `idct`, returns an error if the collection is empty or
+/// has a single item for a type I transform
+pub fn try_idct<F, I, C>(x: &I, dct_type: Type, norm: Normalization) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len(), dct_type.inverse())?;
+    Ok(idct(x, dct_type, norm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_trig_transform;
+    use crate::test_utils as test;
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    #[test]
+    fn test_dct1_vec_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct1/dct1.json", Type::I, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct2_vec_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct2/dct2.json", Type::II, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct3_vec_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct3/dct3.json", Type::III, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct4_vec_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct4/dct4.json", Type::IV, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct1_ortho_arr_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct1_ortho/dct1_ortho.json", Type::I, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct2_ortho_arr_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct2_ortho/dct2_ortho.json", Type::II, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct3_ortho_arr_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct3_ortho/dct3_ortho.json", Type::III, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dct4_ortho_arr_f64() {
+        test_trig_transform!(dct, "datasets/fft/dct4_ortho/dct4_ortho.json", Type::IV, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_idct_roundtrip() {
+        for n in [2, 3, 8, 15, 64, 100] {
+            let x: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin() + 0.5).collect();
+            for dct_type in [Type::I, Type::II, Type::III, Type::IV] {
+                for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+                    let y: Vec<f64> = dct(&x, dct_type, norm);
+                    let z: Vec<f64> = idct(&y, dct_type, norm);
+                    for (i, (&a, &b)) in z.iter().zip(x.iter()).enumerate() {
+                        assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), 
+                            "{:?} {:?} n = {}, {} => {} != {}", dct_type, norm, n, i, a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_dct_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(try_dct::<f64, _, Vec<f64>>(&empty, Type::II, Normalization::Backward), Err(RufftError::EmptyInput));
+        let x = vec![1.0];
+        assert_eq!(try_dct::<f64, _, Vec<f64>>(&x, Type::I, Normalization::Backward), Err(RufftError::UnsupportedLength(1)));
+        assert_eq!(try_idct::<f64, _, Vec<f64>>(&x, Type::I, Normalization::Ortho), Err(RufftError::UnsupportedLength(1)));
+        assert!(try_dct::<f64, _, Vec<f64>>(&x, Type::II, Normalization::Backward).is_ok());
+    }
+}
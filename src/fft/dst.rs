@@ -0,0 +1,212 @@
+//! Discrete sine transforms of types I to IV, computed in `O(n log n)` with the
+//! crate's ffts. Types II to IV are computed from the discrete cosine transform
+//! of the same type and type I from the real fft of the odd symmetric extension
+//! of the input. The unnormalized definitions match scipy's `dst` function e.g.
+//! the type II transform is
+//!
+//! `y[k] = 2 Σ x[n] sin(π(k + 1)(2n + 1) / 2N)`
+//!
+//! and the `norm` argument has the same meaning as scipy's `norm` argument,
+//! with `Normalization::Ortho` giving orthonormal transforms.
+use num_complex::Complex;
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::Normalization;
+use crate::fft::dct::{ dct2, dct3, dct4 };
+use crate::fft::real;
+use crate::traits::{ Iterable, Signal };
+
+pub use crate::fft::dct::Type;
+
+/// Unnormalized type I transform computed from the real fft of the odd 
+/// symmetric extension of `x` of length `2(n + 1)`
+fn dst1<F>(x: &[F]) -> Vec<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let zero = F::zero();
+    let extended: Vec<F> = core::iter::once(zero)
+        .chain(x.iter().cloned())
+        .chain(core::iter::once(zero))
+        .chain(x.iter().rev().map(|&v| -v))
+        .collect();
+    let spectrum: Vec<Complex<F>> = real::rfft(&extended);
+    spectrum.iter().skip(1).take(n).map(|v| -v.im).collect()
+}
+
+/// Computes the discrete sine transform of the given type of the real valued
+/// input collection
+pub fn dst<F, I, C>(x: &I, dst_type: Type, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    let mut x: Vec<F> = x.samples().cloned().collect();
+    let n = x.len();
+    if n == 0 {
+        return C::from_iter(core::iter::empty());
+    }
+    let two = F::one() + F::one();
+    let sqrt2 = two.sqrt();
+    let ortho = norm == Normalization::Ortho;
+    // Alternating signs (-1)^k
+    let sign = |k: usize, v: F| if k.is_even() { v } else { -v };
+
+    let y = match dst_type {
+        Type::I => dst1(&x),
+        Type::II => {
+            // y[k] = dct2((-1)^n x[n])[N - 1 - k]
+            let alternating: Vec<F> = x.samples().enumerate().map(|(k, &v)| sign(k, v)).collect();
+            let mut y: Vec<F> = dct2(&alternating).into_iter().rev().collect();
+            if ortho {
+                y[n - 1] /= sqrt2;
+            }
+            y
+        },
+        Type::III => {
+            // y[k] = (-1)^k dct3(x[N - 1 - n])[k]
+            if ortho {
+                x[n - 1] *= sqrt2;
+            }
+            x.reverse();
+            dct3(&x).into_iter().enumerate().map(|(k, v)| sign(k, v)).collect()
+        },
+        Type::IV => {
+            // y[k] = (-1)^k dct4(x[N - 1 - n])[k]
+            x.reverse();
+            dct4(&x).into_iter().enumerate().map(|(k, v)| sign(k, v)).collect()
+        },
+    };
+
+    let n_logical: F = match dst_type {
+        Type::I => (n + 1).as_(),
+        _ => n.as_(),
+    };
+    let scale = match norm {
+        Normalization::Backward => F::one(),
+        Normalization::Ortho => F::one() / (two * n_logical).sqrt(),
+        Normalization::Forward => F::one() / (two * n_logical),
+    };
+    y.into_iter().map(|v| v * scale).collect()
+}
+
+/// Computes the inverse discrete sine transform of the given type of the real
+/// valued input collection, where `idst(dst(x, t, norm), t, norm) == x`
+pub fn idst<F, I, C>(x: &I, dst_type: Type, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    dst(x, dst_type.inverse(), norm.swap())
+}
+
+/// Fallible version of `dst`, returns an error if the collection is empty
+pub fn try_dst<F, I, C>(x: &I, dst_type: Type, norm: Normalization) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    if x.len() == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    Ok(dst(x, dst_type, norm))
+}
+
+/// Fallible version of `idst`, returns an error if the collection is empty
+pub fn try_idst<F, I, C>(x: &I, dst_type: Type, norm: Normalization) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+{
+    if x.len() == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    Ok(idst(x, dst_type, norm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_trig_transform;
+    use crate::test_utils as test;
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    #[test]
+    fn test_dst1_vec_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst1/dst1.json", Type::I, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst2_vec_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst2/dst2.json", Type::II, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst3_vec_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst3/dst3.json", Type::III, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst4_vec_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst4/dst4.json", Type::IV, Normalization::Backward, f64, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst1_ortho_arr_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst1_ortho/dst1_ortho.json", Type::I, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst2_ortho_arr_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst2_ortho/dst2_ortho.json", Type::II, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst3_ortho_arr_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst3_ortho/dst3_ortho.json", Type::III, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_dst4_ortho_arr_f64() {
+        test_trig_transform!(dst, "datasets/fft/dst4_ortho/dst4_ortho.json", Type::IV, Normalization::Ortho, f64, Array1<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_idst_roundtrip() {
+        for n in [1, 2, 3, 8, 15, 64, 100] {
+            let x: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin() + 0.5).collect();
+            for dst_type in [Type::I, Type::II, Type::III, Type::IV] {
+                for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+                    let y: Vec<f64> = dst(&x, dst_type, norm);
+                    let z: Vec<f64> = idst(&y, dst_type, norm);
+                    for (i, (&a, &b)) in z.iter().zip(x.iter()).enumerate() {
+                        assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), 
+                            "{:?} {:?} n = {}, {} => {} != {}", dst_type, norm, n, i, a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_dst_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(try_dst::<f64, _, Vec<f64>>(&empty, Type::I, Normalization::Backward), Err(RufftError::EmptyInput));
+        assert_eq!(try_idst::<f64, _, Vec<f64>>(&empty, Type::II, Normalization::Backward), Err(RufftError::EmptyInput));
+        assert!(try_dst::<f64, _, Vec<f64>>(&[1.0], Type::I, Normalization::Backward).is_ok());
+    }
+}
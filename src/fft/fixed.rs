@@ -0,0 +1,236 @@
+//! Fixed length radix-2 cooley-tukey transforms for lengths known at compile
+//! time. The twiddle factors of a `FixedFft<F, N>` are computed by `const`
+//! functions during compilation, so a transform declared as a `static` has no
+//! runtime setup and the transforms never allocate, which suits `no_std`
+//! targets that always transform the same number of points.
+//!
+//! `FixedFft` is implemented for `f32` and `f64` and the length `N` must be a
+//! power of two, other lengths fail to compile.
+//!
+//! ```
+//! use rufft::{Complex, fft::fixed::FixedFft};
+//!
+//! static FFT: FixedFft<f32, 64> = FixedFft::new();
+//!
+//! let mut x = [Complex::new(0.0f32, 0.0); 64];
+//! x[1] = Complex::new(1.0, 0.0);
+//! FFT.fft(&mut x);
+//! FFT.ifft(&mut x);
+//! ```
+//!
+//! ```compile_fail
+//! use rufft::fft::fixed::FixedFft;
+//!
+//! // 48 is not a power of two
+//! let _ = FixedFft::<f64, 48>::new();
+//! ```
+use core::f64::consts::FRAC_PI_2;
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::fft::ct::complex::fft_with_twiddles;
+use crate::fft::scale;
+
+/// Computes `(sin(x), cos(x))` from their taylor series, accurate to double
+/// precision for `|x| <= π/4`
+const fn sin_cos(x: f64) -> (f64, f64) {
+    let x2 = x * x;
+    let mut sin = x;
+    let mut cos = 1.0;
+    let mut sin_term = x;
+    let mut cos_term = 1.0;
+    let mut i = 1;
+    while i < 12 {
+        let even = (2 * i) as f64;
+        sin_term *= -x2 / (even * (even + 1.0));
+        cos_term *= -x2 / ((even - 1.0) * even);
+        sin += sin_term;
+        cos += cos_term;
+        i += 1;
+    }
+    (sin, cos)
+}
+
+/// Computes the twiddle factor `exp(-2πik/n)` as `(re, im)`. The angle is
+/// reduced to the first octant using the exact integer ratio `k / n` so the
+/// series is only evaluated where it is accurate
+pub(crate) const fn twiddle(k: usize, n: usize) -> (f64, f64) {
+    let k = k % n;
+    let quadrant = 4 * k / n;
+    let rem = 4 * k - quadrant * n;
+    let (sin, cos) = if 2 * rem <= n {
+        sin_cos(FRAC_PI_2 * rem as f64 / n as f64)
+    } else {
+        let (sin, cos) = sin_cos(FRAC_PI_2 * (n - rem) as f64 / n as f64);
+        (cos, sin)
+    };
+    let (cos, sin) = match quadrant {
+        0 => (cos, sin),
+        1 => (-sin, cos),
+        2 => (-cos, -sin),
+        _ => (sin, -cos),
+    };
+    (cos, -sin)
+}
+
+/// Computes the `n / 2` twiddle factors of a length `n` transform in double
+/// precision, the remaining items of the table are zero
+const fn twiddles_f64<const N: usize>() -> [Complex<f64>; N] {
+    let mut table = [Complex::new(0.0, 0.0); N];
+    let mut k = 0;
+    while k < N / 2 {
+        let (re, im) = twiddle(k, N);
+        table[k] = Complex::new(re, im);
+        k += 1;
+    }
+    table
+}
+
+/// Computes the `n / 2` twiddle factors of a length `n` transform in single
+/// precision, rounded from the double precision factors
+const fn twiddles_f32<const N: usize>() -> [Complex<f32>; N] {
+    let wide = twiddles_f64::<N>();
+    let mut table = [Complex::new(0.0, 0.0); N];
+    let mut k = 0;
+    while k < N / 2 {
+        table[k] = Complex::new(wide[k].re as f32, wide[k].im as f32);
+        k += 1;
+    }
+    table
+}
+
+/// A radix-2 fast fourier transform of the fixed length `N` with a twiddle
+/// table computed at compile time. Only the first `N / 2` items of the table
+/// are used, the table has length `N` as array lengths can not yet be computed
+/// from generic constants
+#[derive(Debug, Clone)]
+pub struct FixedFft<F, const N: usize> {
+    twiddles: [Complex<F>; N],
+}
+
+/// Floating point types with a compile time table of the twiddle factors
+/// `exp(-2πik/N)` for `k < N / 2` of a length `N` transform, implemented for
+/// `f32` and `f64`
+pub trait TwiddleTable<const N: usize>: Sized {
+    const TWIDDLES: [Complex<Self>; N];
+}
+
+impl<const N: usize> TwiddleTable<N> for f32 {
+    const TWIDDLES: [Complex<f32>; N] = twiddles_f32::<N>();
+}
+
+impl<const N: usize> TwiddleTable<N> for f64 {
+    const TWIDDLES: [Complex<f64>; N] = twiddles_f64::<N>();
+}
+
+impl<F: TwiddleTable<N>, const N: usize> FixedFft<F, N> {
+    /// Fails to compile if `N` is not a power of two
+    const VALID_LEN: () = assert!(N.is_power_of_two(), "FixedFft requires a power of two length");
+
+    /// Creates the transform, usable in `const` and `static` items
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LEN;
+        Self { twiddles: F::TWIDDLES }
+    }
+}
+
+impl<F: TwiddleTable<N>, const N: usize> Default for FixedFft<F, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F, const N: usize> FixedFft<F, N>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    /// The length of the transform
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns true if the transform has length zero, which is never the case
+    /// as zero is not a power of two
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// The `N / 2` twiddle factors used by the transform
+    pub fn twiddles(&self) -> &[Complex<F>] {
+        &self.twiddles[..N / 2]
+    }
+
+    /// Computes the in-place forward transform of the array. The output is
+    /// *not* normalized
+    pub fn fft(&self, x: &mut [Complex<F>; N]) {
+        fft_with_twiddles(x, self.twiddles());
+    }
+
+    /// Computes the in-place inverse transform of the array. The output *is*
+    /// normalized
+    pub fn ifft(&self, x: &mut [Complex<F>; N]) {
+        // ifft(x) = conj(fft(conj(x))) / n
+        x.iter_mut().for_each(|v| *v = v.conj());
+        fft_with_twiddles(x, self.twiddles());
+        x.iter_mut().for_each(|v| *v = v.conj());
+        scale(x, F::one() / N.as_());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::complex::dft;
+    use crate::fft::ct::complex::fft_in_place;
+    use crate::test_utils as test;
+
+    const ATOL_F32: f32 = 1e-3;
+    const RTOL_F32: f32 = 1e-4;
+
+    fn signal<const N: usize>() -> [Complex<f64>; N] {
+        test::signal(N).try_into().expect("The signal has N samples")
+    }
+
+    #[test]
+    fn test_twiddles() {
+        let fft = FixedFft::<f64, 1024>::new();
+        for (k, w) in fft.twiddles().iter().enumerate() {
+            let expected = Complex::from_polar(1.0, -core::f64::consts::TAU * k as f64 / 1024.0);
+            assert!((w - expected).norm() < 1e-15, "{} => {} != {}", k, w, expected);
+        }
+    }
+
+    #[test]
+    fn test_fft_f64() {
+        fn check<const N: usize>() {
+            let fft = FixedFft::<f64, N>::new();
+            let x = signal::<N>();
+            let mut expected = x;
+            fft_in_place(&mut expected);
+            let mut y = x;
+            fft.fft(&mut y);
+            test::assert_nearly_equal(&y, &expected);
+            fft.ifft(&mut y);
+            test::assert_nearly_equal(&y, &x);
+        }
+        check::<1>();
+        check::<2>();
+        check::<64>();
+        check::<256>();
+        check::<1024>();
+    }
+
+    #[test]
+    fn test_fft_static_f32() {
+        static FFT: FixedFft<f32, 256> = FixedFft::new();
+        let x = signal::<256>();
+        let expected: Vec<Complex<f64>> = dft(&x.to_vec());
+        let mut y: [Complex<f32>; 256] = core::array::from_fn(|i| Complex::new(x[i].re as f32, x[i].im as f32));
+        FFT.fft(&mut y);
+        for (i, (a, b)) in y.iter().zip(expected.iter()).enumerate() {
+            let b = Complex::new(b.re as f32, b.im as f32);
+            assert!(test::nearly_equal_complex(*a, b, RTOL_F32, ATOL_F32), "{} => {} != {}", i, a, b);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Fixed-point radix-2 cooley-tukey transforms of Q15 (`i16`) and Q31 (`i32`)
+//! samples for targets without a floating point unit. A Q15 value `v`
+//! represents the real number `v / 2^15` and a Q31 value `v / 2^31`.
+//!
+//! Each butterfly stage can grow the magnitude of the samples by up to a factor
+//! of `1 + √2`, so the transform uses block floating point to avoid overflow.
+//! Before every stage the largest component of the block is checked and the
+//! outputs of the stage are shifted right by the number of bits needed to keep
+//! them in range. The total number of bits shifted is returned as the scale
+//! exponent, the transform of the input is the output multiplied by
+//! `2^exponent`.
+//!
+//! The twiddle factors are read from a quarter wave sine table computed at
+//! compile time, so the transforms only use integer arithmetic at runtime.
+//! Transforms of power of two lengths up to `MAX_LEN` are supported.
+//!
+//! ```
+//! use rufft::{Complex, fft::fixed_point};
+//!
+//! // A Q15 impulse of amplitude 0.5
+//! let mut x = [Complex::new(0i16, 0); 8];
+//! x[0] = Complex::new(1 << 14, 0);
+//! let exponent = fixed_point::fft(&mut x).unwrap();
+//! // The spectrum is flat with a value of 0.5 in every bin
+//! for v in x.iter() {
+//!     assert_eq!((v.re as i32) << exponent, 1 << 14);
+//! }
+//! ```
+use num_complex::Complex;
+use num_traits::{ PrimInt, Signed, AsPrimitive, One, Zero };
+use crate::error::RufftError;
+use crate::fft::ct::complex::{ bit_reverse_permute, check_len };
+use crate::fft::fixed::twiddle;
+
+/// The largest transform length supported by the fixed-point transforms
+pub const MAX_LEN: usize = 4096;
+
+/// Length of a quarter period of the sine table
+const QUARTER: usize = MAX_LEN / 4;
+
+/// `sin(2πj / MAX_LEN)` for `j <= MAX_LEN / 4` in Q31 format, rounded to the
+/// nearest value and saturated at `i32::MAX`
+const SINE: [i32; QUARTER + 1] = sine_table();
+
+const fn sine_table() -> [i32; QUARTER + 1] {
+    let mut table = [0; QUARTER + 1];
+    let mut j = 0;
+    while j <= QUARTER {
+        let (_, im) = twiddle(j, MAX_LEN);
+        // Float to integer casts saturate
+        table[j] = (-im * 2147483648.0 + 0.5) as i32;
+        j += 1;
+    }
+    table
+}
+
+/// Signed integer Q-format sample types. The butterflies are computed in the
+/// `Wide` type which holds the product of two samples without overflow
+pub trait QFormat: PrimInt + Signed + AsPrimitive<Self::Wide> {
+    /// Integer type with at least twice the bits of the sample type
+    type Wide: PrimInt + Signed + AsPrimitive<Self>;
+    /// Number of fractional bits of the format
+    const FRAC_BITS: u32;
+
+    /// Converts a Q31 value to the format, rounding to the nearest value
+    fn from_q31(v: i32) -> Self;
+}
+
+impl QFormat for i16 {
+    type Wide = i32;
+    const FRAC_BITS: u32 = 15;
+
+    fn from_q31(v: i32) -> Self {
+        ((v as i64 + (1 << 15)) >> 16).min(i16::MAX as i64) as i16
+    }
+}
+
+impl QFormat for i32 {
+    type Wide = i64;
+    const FRAC_BITS: u32 = 31;
+
+    fn from_q31(v: i32) -> Self {
+        v
+    }
+}
+
+/// Twiddle factor `exp(-2πik/n)` for `k < n / 2` from the sine table. `n` must
+/// be a power of two no larger than `MAX_LEN`
+fn table_twiddle<Q: QFormat>(k: usize, n: usize) -> Complex<Q> {
+    let j = k * (MAX_LEN / n);
+    let (cos, sin) = if j <= QUARTER {
+        (SINE[QUARTER - j], SINE[j])
+    } else {
+        (-SINE[j - QUARTER], SINE[2 * QUARTER - j])
+    };
+    Complex::new(Q::from_q31(cos), -Q::from_q31(sin))
+}
+
+/// Shifts the value right by `shift` bits, rounding to the nearest value
+fn round_shift<W: PrimInt>(v: W, shift: u32) -> W {
+    if shift == 0 {
+        v
+    } else {
+        (v + (W::one() << (shift as usize - 1))) >> shift as usize
+    }
+}
+
+/// Narrows the wide value to the sample type, saturating at its limits
+fn saturate<Q: QFormat>(v: Q::Wide) -> Q {
+    v.max(Q::min_value().as_()).min(Q::max_value().as_()).as_()
+}
+
+/// Number of bits the outputs of a butterfly stage must be shifted by so that
+/// no output exceeds the range of the format, given the largest absolute value
+/// of the real and imaginary parts of the block
+fn stage_shift<Q: QFormat>(max: Q::Wide) -> u32 {
+    // The outputs grow by at most 1 + √2 < 5 / 2
+    let two = Q::Wide::one() + Q::Wide::one();
+    let five = two + two + Q::Wide::one();
+    let limit: Q::Wide = Q::max_value().as_();
+    let mut shift = 0;
+    while shift < 2 && five * max > (two * limit) << shift as usize {
+        shift += 1;
+    }
+    shift
+}
+
+/// Computes the in-place fixed-point radix-2 fast fourier transform of the
+/// complex valued slice of Q15 or Q31 samples with block floating point
+/// scaling. Returns the scale exponent, the transform of the input is the
+/// output multiplied by `2^exponent`. The length of the slice must be a
+/// power of two no larger than `MAX_LEN`
+pub fn fft<Q: QFormat>(x: &mut [Complex<Q>]) -> Result<u32, RufftError> {
+    let n = x.len();
+    check_len(n)?;
+    if n > MAX_LEN {
+        return Err(RufftError::UnsupportedLength(n));
+    }
+    let frac_bits = Q::FRAC_BITS as usize;
+    let half_lsb = Q::Wide::one() << (frac_bits - 1);
+    bit_reverse_permute(x);
+
+    let mut exponent = 0;
+    let mut len = 2;
+    while len <= n {
+        let max = x.iter()
+            .map(|v| {
+                let re: Q::Wide = v.re.as_();
+                let im: Q::Wide = v.im.as_();
+                re.abs().max(im.abs())
+            })
+            .fold(Q::Wide::zero(), |a, b| a.max(b));
+        let shift = stage_shift::<Q>(max);
+        let half = len / 2;
+        for chunk in x.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for (k, (even, odd)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let w = table_twiddle::<Q>(k, len);
+                let (wr, wi): (Q::Wide, Q::Wide) = (w.re.as_(), w.im.as_());
+                let (ar, ai): (Q::Wide, Q::Wide) = (even.re.as_(), even.im.as_());
+                let (br, bi): (Q::Wide, Q::Wide) = (odd.re.as_(), odd.im.as_());
+                // The product of two samples has twice the fractional bits
+                let tr = (br * wr - bi * wi + half_lsb) >> frac_bits;
+                let ti = (br * wi + bi * wr + half_lsb) >> frac_bits;
+                *even = Complex::new(
+                    saturate::<Q>(round_shift(ar + tr, shift)),
+                    saturate::<Q>(round_shift(ai + ti, shift)),
+                );
+                *odd = Complex::new(
+                    saturate::<Q>(round_shift(ar - tr, shift)),
+                    saturate::<Q>(round_shift(ai - ti, shift)),
+                );
+            }
+        }
+        exponent += shift;
+        len <<= 1;
+    }
+    Ok(exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::ct::complex::fft_in_place;
+
+    /// Transforms the Q-format signal with the fixed-point and float ffts and
+    /// returns the ratio of the error norm to the signal norm and the exponent
+    fn relative_error<Q>(x: &[Complex<Q>]) -> (f64, u32)
+    where
+        Q: QFormat + AsPrimitive<f64>,
+    {
+        let unit = 2f64.powi(Q::FRAC_BITS as i32);
+        let to_f64 = |v: Q| -> f64 { v.as_() };
+        let mut expected: Vec<Complex<f64>> = x.iter()
+            .map(|v| Complex::new(to_f64(v.re) / unit, to_f64(v.im) / unit))
+            .collect();
+        fft_in_place(&mut expected);
+        let mut y = x.to_vec();
+        let exponent = fft(&mut y).unwrap();
+        let scale = 2f64.powi(exponent as i32) / unit;
+        let (error, norm) = y.iter()
+            .zip(expected.iter())
+            .map(|(a, b)| {
+                let a = Complex::new(to_f64(a.re) * scale, to_f64(a.im) * scale);
+                ((a - b).norm_sqr(), b.norm_sqr())
+            })
+            .fold((0.0, 0.0), |(e, n), (a, b)| (e + a, n + b));
+        ((error / norm).sqrt(), exponent)
+    }
+
+    /// Broadband signal with parts in `[-amplitude, amplitude]`. Unlike the
+    /// two tones of `test_utils::signal` the power is spread over several
+    /// frequencies, which the error bounds of the Q15 transform depend on
+    fn signal(n: usize, amplitude: f64) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                let v = Complex::new(
+                    0.6 * (0.3 * t).sin() + 0.4 * (2.1 * t).cos(),
+                    0.5 * (0.7 * t).cos() - 0.5 * (1.3 * t).sin(),
+                );
+                v * amplitude
+            })
+            .collect()
+    }
+
+    fn quantize<Q>(x: &[Complex<f64>]) -> Vec<Complex<Q>>
+    where
+        Q: QFormat,
+        f64: AsPrimitive<Q>,
+    {
+        let unit = 2f64.powi(Q::FRAC_BITS as i32);
+        x.iter()
+            .map(|v| Complex::new((v.re * unit).round().as_(), (v.im * unit).round().as_()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sine_table() {
+        for (j, &v) in SINE.iter().enumerate() {
+            let expected = (core::f64::consts::TAU * j as f64 / MAX_LEN as f64).sin() * 2f64.powi(31);
+            assert!((v as f64 - expected).abs() <= 1.0, "{} => {} != {}", j, v, expected);
+        }
+    }
+
+    #[test]
+    fn test_fft_q15() {
+        for n in [2, 4, 16, 64, 256, 1024, MAX_LEN] {
+            for amplitude in [0.99, 0.1] {
+                let x = quantize::<i16>(&signal(n, amplitude));
+                let (error, exponent) = relative_error(&x);
+                assert!(error < 2e-3, "n = {}, amplitude = {} => error {}", n, amplitude, error);
+                assert!(exponent <= 2 * n.trailing_zeros(), "n = {} => exponent {}", n, exponent);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_q31() {
+        for n in [2, 4, 16, 64, 256, 1024, MAX_LEN] {
+            for amplitude in [0.99, 0.1] {
+                let x = quantize::<i32>(&signal(n, amplitude));
+                let (error, _) = relative_error(&x);
+                assert!(error < 1e-7, "n = {}, amplitude = {} => error {}", n, amplitude, error);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_full_scale() {
+        // The largest possible components must not overflow
+        let mut x = vec![Complex::new(i16::MAX, i16::MIN); 64];
+        x[1] = Complex::new(i16::MIN, i16::MAX);
+        let (error, _) = relative_error(&x);
+        assert!(error < 2e-3, "error {}", error);
+        let x = vec![Complex::new(i32::MIN, i32::MIN); 64];
+        let (error, _) = relative_error(&x);
+        assert!(error < 1e-7, "error {}", error);
+    }
+
+    #[test]
+    fn test_fft_invalid_len() {
+        let mut x = vec![Complex::new(0i16, 0); 12];
+        assert_eq!(fft(&mut x), Err(RufftError::UnsupportedLength(12)));
+        let mut x = vec![Complex::new(0i32, 0); 2 * MAX_LEN];
+        assert_eq!(fft(&mut x), Err(RufftError::UnsupportedLength(2 * MAX_LEN)));
+        let mut x: Vec<Complex<i16>> = vec![];
+        assert_eq!(fft(&mut x), Err(RufftError::EmptyInput));
+    }
+}
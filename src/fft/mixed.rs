@@ -0,0 +1,188 @@
+pub mod complex;
+#[cfg(feature = "alloc")]
+use num_complex::Complex;
+#[cfg(feature = "alloc")]
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+#[cfg(feature = "alloc")]
+use crate::error::RufftError;
+#[cfg(feature = "alloc")]
+use crate::fft::{ resize, scale, Normalization };
+#[cfg(feature = "alloc")]
+use crate::traits::{ Iterable, IterableMut, Signal };
+
+pub use complex::is_supported_len;
+
+/// Computes the mixed-radix cooley-tukey fast fourier transform of the real 
+/// valued input collection. The length of the collection must only have 2, 3, 
+/// 5 and 7 as prime factors
+#[cfg(feature = "alloc")]
+pub fn fft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    fft_norm(x, Normalization::Backward)
+}
+
+/// Computes the mixed-radix cooley-tukey fast fourier transform of the real
+/// valued input collection with the given normalization
+#[cfg(feature = "alloc")]
+pub fn fft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let zero = F::zero();
+    let mut y: C = complex::forward(x.samples().map(|&x| Complex::new(x, zero)), n);
+    scale(y.as_mut_slice(), norm.forward_scale(n));
+    y
+}
+
+/// Computes the mixed-radix cooley-tukey fast fourier transform of length `n` 
+/// of the real valued input collection, which is zero padded or truncated to 
+/// length `n`. Equivalent to numpy's `fft(x, n)`, `n` must only have 2, 3, 5 
+/// and 7 as prime factors
+#[cfg(feature = "alloc")]
+pub fn fft_n<F, I, C>(x: &I, n: usize) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    complex::forward(resize(x.samples().cloned(), zero, n).map(|x| Complex::new(x, zero)), n)
+}
+
+/// Compute the inverse mixed-radix cooley-tukey fast fourier transform of the 
+/// complex input collection and returns the real valued output collection
+/// The output *is* normalized.
+#[cfg(feature = "alloc")]
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    ifft_norm(x, Normalization::Backward)
+}
+
+/// Compute the inverse mixed-radix cooley-tukey fast fourier transform of the
+/// complex input collection with the given normalization and returns the real
+/// valued output collection
+#[cfg(feature = "alloc")]
+pub fn ifft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    let y: I = complex::ifft_norm(x, norm);
+    y.iter().map(|x| x.re).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length has a prime factor other than 2, 3, 5 and 7
+#[cfg(feature = "alloc")]
+pub fn try_fft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length has a prime factor other than 2, 3, 5 and 7
+#[cfg(feature = "alloc")]
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ test_fft, test_ifft };
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-12;
+    const RTOL_F64: f64 = 1e-9;
+
+    // Really loose tolerances for f32 because we're checking complex numbers
+    // which is more difficult, especially near zero where the phase can suddenly
+    // jump by π for a small change in the real or imaginary part. Precision errors
+    // for FFT algorithms can also accumulate. These values were found by trial-and-error.
+    const ATOL_F32: f32 = 1e-1;
+    const RTOL_F32: f32 = 1e-1;
+
+    #[test]
+    fn test_fft_mixed_vec_func_f64() {
+        test_fft!(f64, Vec<f64>, Vec<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+    #[test]
+    fn test_fft_mixed_arr_func_f64() {
+        test_fft!(f64, Array1<f64>, Array1<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+    #[test]
+    fn test_fft_mixed_mix1_func_f64() {
+        test_fft!(f64, Vec<f64>, Array1<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+    #[test]
+    fn test_fft_mixed_vec_func_f32() {
+        test_fft!(f32, Vec<f32>, Vec<Complex<f32>>, RTOL_F32, ATOL_F32);
+    }
+    #[test]
+    fn test_ifft_mixed_vec_f64() {
+        test_ifft!(f64, Vec<Complex<f64>>, Vec<f64>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_n_pads_and_truncates() {
+        let x: Vec<f64> = (0..100).map(|i| (i as f64 * 0.3).sin()).collect();
+        for n in [6, 60, 105, 120] {
+            let output: Vec<Complex<f64>> = fft_n(&x, n);
+            let resized: Vec<f64> = x.iter().cloned().chain(core::iter::repeat(0.0)).take(n).collect();
+            let reference: Vec<Complex<f64>> = crate::fft::dft(&resized);
+            assert_eq!(output.len(), n);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_fft_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&empty), Err(RufftError::EmptyInput));
+        let x: Vec<f64> = vec![1.0; 22];
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&x), Err(RufftError::UnsupportedLength(22)));
+        let x: Vec<f64> = vec![1.0; 210];
+        assert!(try_fft::<f64, _, Vec<Complex<f64>>>(&x).is_ok());
+    }
+}
@@ -0,0 +1,401 @@
+use num_complex::Complex;
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use crate::error::RufftError;
+#[cfg(feature = "alloc")]
+use crate::fft::{ resize, scale, Normalization };
+#[cfg(feature = "alloc")]
+use crate::traits::{ Iterable, IterableMut };
+
+/// Returns the radix used to split a transform of length `n`, preferring the
+/// radix-4 butterfly where possible. Returns `None` if `n` has a prime factor
+/// which is not 2, 3, 5 or 7
+fn radix(n: usize) -> Option<usize> {
+    [4, 2, 3, 5, 7].into_iter().find(|p| Integer::is_multiple_of(&n, p))
+}
+
+/// Returns true if a transform of length `n` is a single butterfly of the
+/// mixed-radix kernel, which needs no twiddle factors
+#[cfg(feature = "std")]
+pub(crate) fn is_butterfly_len(n: usize) -> bool {
+    matches!(n, 2 | 3 | 4 | 5 | 7)
+}
+
+/// Returns true if the length `n` factors into the radices supported by the 
+/// mixed-radix algorithm i.e. 2, 3, 4, 5 and 7
+pub fn is_supported_len(mut n: usize) -> bool {
+    if n == 0 {
+        return false;
+    }
+    while n > 1 {
+        match radix(n) {
+            Some(p) => n /= p,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Checks that the length is supported by the mixed-radix algorithm
+#[cfg(feature = "alloc")]
+pub(crate) fn check_len(n: usize) -> Result<(), RufftError> {
+    if n == 0 {
+        Err(RufftError::EmptyInput)
+    } else if !is_supported_len(n) {
+        Err(RufftError::UnsupportedLength(n))
+    } else {
+        Ok(())
+    }
+}
+
+/// Computes the `n` twiddle factors used by the mixed-radix kernel for a 
+/// transform of length `n`
+pub(crate) fn twiddles<F>(n: usize) -> impl Iterator<Item = Complex<F>>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let theta = -F::TAU() / n.as_();
+    (0..n).map(move |k| Complex::from_polar(F::one(), theta * k.as_()))
+}
+
+/// Recursive decimation in time kernel. Computes the forward transform of the
+/// items `input[i * stride]` for `i` in `0..output.len()` into `output`, where
+/// `twiddles` are the twiddle factors of the full length transform
+pub(crate) fn process<F>(
+    input: &[Complex<F>], 
+    output: &mut [Complex<F>], 
+    stride: usize, 
+    twiddles: &[Complex<F>]
+)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = output.len();
+    if n == 1 {
+        output[0] = input[0];
+        return;
+    }
+    let p = radix(n).expect("Length must only have 2, 3, 5 and 7 as prime factors");
+    let m = n / p;
+    for (q, sub) in output.chunks_exact_mut(m).enumerate() {
+        process(&input[q * stride..], sub, stride * p, twiddles);
+    }
+    match p {
+        2 => butterfly2(output, m, stride, twiddles),
+        4 => butterfly4(output, m, stride, twiddles),
+        3 => butterfly_odd::<F, 3, 1>(output, m, stride, twiddles),
+        5 => butterfly_odd::<F, 5, 2>(output, m, stride, twiddles),
+        7 => butterfly_odd::<F, 7, 3>(output, m, stride, twiddles),
+        _ => unreachable!(),
+    }
+}
+
+fn butterfly2<F>(x: &mut [Complex<F>], m: usize, stride: usize, twiddles: &[Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+{
+    let (lo, hi) = x.split_at_mut(m);
+    for (k, (a, b)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+        let t = *b * twiddles[k * stride];
+        *b = *a - t;
+        *a += t;
+    }
+}
+
+fn butterfly4<F>(x: &mut [Complex<F>], m: usize, stride: usize, twiddles: &[Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+{
+    for k in 0..m {
+        let t0 = x[k];
+        let t1 = x[k + m] * twiddles[k * stride];
+        let t2 = x[k + 2 * m] * twiddles[2 * k * stride];
+        let t3 = x[k + 3 * m] * twiddles[3 * k * stride];
+
+        let a0 = t0 + t2;
+        let a1 = t0 - t2;
+        let a2 = t1 + t3;
+        // Multiplying by -i for the forward transform
+        let a3 = t1 - t3;
+        let a3 = Complex::new(a3.im, -a3.re);
+
+        x[k] = a0 + a2;
+        x[k + m] = a1 + a3;
+        x[k + 2 * m] = a0 - a2;
+        x[k + 3 * m] = a1 - a3;
+    }
+}
+
+/// Butterfly for the odd prime radix `P = 2R + 1`. Pairs the symmetric terms
+/// `t[j]` and `t[P - j]` so only `R` cosines and sines are needed per output.
+/// The cosines and sines of multiples of `2π / P` are read from the twiddle 
+/// factors of the full length transform, whose length is `P * m * stride`
+fn butterfly_odd<F, const P: usize, const R: usize>(
+    x: &mut [Complex<F>], 
+    m: usize, 
+    stride: usize, 
+    twiddles: &[Complex<F>]
+)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+{
+    let zero = Complex::new(F::zero(), F::zero());
+    let mut cos = [[F::zero(); R]; R];
+    let mut sin = [[F::zero(); R]; R];
+    for s in 0..R {
+        for j in 0..R {
+            // The twiddle factor is exp(-2πi(j + 1)(s + 1) / P)
+            let w = twiddles[((j + 1) * (s + 1) % P) * m * stride];
+            cos[s][j] = w.re;
+            sin[s][j] = -w.im;
+        }
+    }
+
+    let mut t = [zero; P];
+    for k in 0..m {
+        for (q, tq) in t.iter_mut().enumerate() {
+            *tq = x[k + q * m] * twiddles[q * k * stride];
+        }
+        let mut sums = [zero; R];
+        let mut diffs = [zero; R];
+        for j in 0..R {
+            sums[j] = t[j + 1] + t[P - 1 - j];
+            diffs[j] = t[j + 1] - t[P - 1 - j];
+        }
+
+        x[k] = t[0] + sums.iter().fold(zero, |acc, &v| acc + v);
+        for s in 0..R {
+            let mut a = t[0];
+            let mut b = zero;
+            for j in 0..R {
+                a += sums[j] * cos[s][j];
+                b += diffs[j] * sin[s][j];
+            }
+            // Multiplying b by -i for the forward transform
+            let b = Complex::new(b.im, -b.re);
+            x[k + (s + 1) * m] = a + b;
+            x[k + (P - 1 - s) * m] = a - b;
+        }
+    }
+}
+
+/// Computes the unnormalized forward transform of the `n` items of the iterator
+/// into a new collection. A single scratch buffer holds a copy of the input
+/// followed by the twiddle factors
+#[cfg(feature = "alloc")]
+pub(crate) fn forward<F, C>(x: impl Iterator<Item = Complex<F>>, n: usize) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    assert!(is_supported_len(n), "The mixed-radix fft does not support a length of {}", n);
+    let zero = Complex::new(F::zero(), F::zero());
+    let mut scratch: Vec<Complex<F>> = x.take(n).chain(twiddles(n)).collect();
+    let (input, twiddles) = scratch.split_at_mut(n);
+    let mut y = C::from_iter(core::iter::repeat_n(zero, n));
+    process(input, y.as_mut_slice(), 1, twiddles);
+    y
+}
+
+/// Computes the mixed-radix cooley-tukey fast fourier transform on the complex 
+/// valued input collection and outputs a complex valued collection. The length
+/// of the collection must only have 2, 3, 5 and 7 as prime factors
+#[cfg(feature = "alloc")]
+pub fn fft<F, I>(x: &I) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    fft_norm(x, Normalization::Backward)
+}
+
+/// Computes the mixed-radix cooley-tukey fast fourier transform on the complex
+/// valued input collection with the given normalization and outputs a complex
+/// valued collection
+#[cfg(feature = "alloc")]
+pub fn fft_norm<F, I>(x: &I, norm: Normalization) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let mut y: I = forward(x.iter().cloned(), n);
+    scale(y.as_mut_slice(), norm.forward_scale(n));
+    y
+}
+
+/// Computes the mixed-radix cooley-tukey fast fourier transform of length `n` 
+/// on the complex valued input collection, which is zero padded or truncated 
+/// to length `n`, and outputs a complex valued collection. `n` must only have 
+/// 2, 3, 5 and 7 as prime factors
+#[cfg(feature = "alloc")]
+pub fn fft_n<F, I>(x: &I, n: usize) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = Complex::new(F::zero(), F::zero());
+    forward(resize(x.iter().cloned(), zero, n), n)
+}
+
+/// Computes the mixed-radix cooley-tukey inverse fast fourier transform on the
+/// complex valued input collection and outputs a complex valued collection. 
+/// The output *is* normalized
+#[cfg(feature = "alloc")]
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    ifft_norm(x, Normalization::Backward)
+}
+
+/// Computes the mixed-radix cooley-tukey inverse fast fourier transform on the
+/// complex valued input collection with the given normalization and outputs a 
+/// complex valued collection
+#[cfg(feature = "alloc")]
+pub fn ifft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    // The inverse transform is computed as conj(fft(conj(x))) scaled by 1/n
+    // with the default normalization
+    let n = x.len();
+    let factor: F = norm.inverse_scale(n);
+    let y: I = forward(x.iter().map(|v| v.conj()), n);
+    y.iter().map(|v| v.conj().scale(factor)).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length has a prime factor other than 2, 3, 5 and 7
+#[cfg(feature = "alloc")]
+pub fn try_fft<F, I>(x: &I) -> Result<I, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length has a prime factor other than 2, 3, 5 and 7
+#[cfg(feature = "alloc")]
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ test_complex_fft, test_complex_ifft };
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-12;
+    const RTOL_F64: f64 = 1e-9;
+
+    // Really loose tolerances for f32 because we're checking complex numbers
+    // which is more difficult, especially near zero where the phase can suddenly
+    // jump by π for a small change in the real or imaginary part. Precision errors
+    // for FFT algorithms can also accumulate. These values were found by trial-and-error.
+    const ATOL_F32: f32 = 1e-1;
+    const RTOL_F32: f32 = 1e-1;
+
+    #[test]
+    fn test_fft_mixed_vec_func_f64() {
+        test_complex_fft!(f64, Vec<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_mixed_arr_func_f64() {
+        test_complex_fft!(f64, Array1<Complex<f64>>,  RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_mixed_vec_func_f32() {
+        test_complex_fft!(f32, Vec<Complex<f32>>, RTOL_F32, ATOL_F32);
+    }
+
+    #[test]
+    fn test_ifft_mixed_vec_f64() {
+        test_complex_ifft!(f64, Vec<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_ifft_mixed_arr_f64() {
+        test_complex_ifft!(f64, Array1<Complex<f64>>, RTOL_F64, ATOL_F64);
+    }
+
+    #[test]
+    fn test_fft_mixed_matches_dft() {
+        for n in [1, 3, 5, 7, 12, 49, 60, 210, 343, 1000] {
+            let x: Vec<Complex<f64>> = (0..n)
+                .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+                .collect();
+            let output = fft(&x);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, 1e-10), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_norm_matches_dft_norm_f64() {
+        let x: Vec<Complex<f64>> = (0..60).map(|i| Complex::new((i as f64).sin(), (i as f64).cos())).collect();
+        for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+            let output = fft_norm(&x, norm);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft_norm(&x, norm);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                    "{} => {} != {}", i, a, b);
+            }
+            let y: Vec<Complex<f64>> = ifft_norm(&output, norm);
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(crate::test_utils::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                    "{} => {} != {}", i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_supported_len() {
+        assert!(is_supported_len(1000));
+        assert!(is_supported_len(44100));
+        assert!(is_supported_len(48000));
+        assert!(!is_supported_len(0));
+        assert!(!is_supported_len(11));
+        assert!(!is_supported_len(2 * 3 * 13));
+    }
+}
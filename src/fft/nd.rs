@@ -0,0 +1,459 @@
+//! Multidimensional fast fourier transforms for `ndarray` arrays of any dimension,
+//! including views. Each transform is computed along one axis at a time, lane by
+//! lane, with a single `Plan` per axis so the twiddle factors are only computed
+//! once for all the lanes. The input may be real or complex valued and the
+//! output is always a complex valued array in standard layout.
+//!
+//! ```
+//! use rufft::{Complex, fft::nd};
+//! use ndarray::Array2;
+//!
+//! let image = Array2::<f64>::ones((4, 6));
+//! let spectrum: Array2<Complex<f64>> = nd::fft2(&image);
+//! let _: Array2<Complex<f64>> = nd::ifft2(&spectrum);
+//! ```
+use ndarray::{ Array, ArrayBase, Axis, Data, Dimension };
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::{ plan::{ Direction, Plan }, Normalization };
+#[cfg(feature = "rayon")]
+use crate::fft::parallel;
+
+/// Transforms every lane of `x` along `axis` in-place
+fn process_axis<F, D>(x: &mut Array<Complex<F>, D>, axis: Axis, direction: Direction, norm: Normalization)
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len_of(axis);
+    let mut plan = Plan::with_normalization(n, direction, norm);
+    #[cfg(feature = "rayon")]
+    if process_axis_parallel(x, axis, &mut plan) {
+        return;
+    }
+    let mut buf = vec![Complex::new(F::zero(), F::zero()); n];
+    for mut lane in x.lanes_mut(axis) {
+        match lane.as_slice_mut() {
+            Some(slice) => plan.process(slice),
+            None => {
+                // Lanes which aren't contiguous are copied into a buffer
+                buf.iter_mut().zip(lane.iter()).for_each(|(b, l)| *b = *l);
+                plan.process(&mut buf);
+                lane.iter_mut().zip(buf.iter()).for_each(|(l, b)| *l = *b);
+            }
+        }
+    }
+}
+
+/// Transforms every lane of `x` along `axis` in-place on the rayon thread pool.
+/// Contiguous lanes are transformed on the storage of the array, otherwise the
+/// lanes are copied into a standard layout array with the axis last. Returns
+/// false if the float type is not `f32` or `f64`
+#[cfg(feature = "rayon")]
+fn process_axis_parallel<F, D>(x: &mut Array<Complex<F>, D>, axis: Axis, plan: &mut Plan<F>) -> bool
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    if x.len_of(axis) <= 1 || x.stride_of(axis) == 1 {
+        let lanes = x.lanes_mut(axis)
+            .into_iter()
+            .map(|lane| lane.into_slice().expect("The lanes are contiguous"))
+            .collect();
+        parallel::process_lanes(plan, lanes)
+    } else if parallel::is_parallel::<F>() {
+        let mut view = x.view_mut();
+        view.swap_axes(axis.index(), view.ndim() - 1);
+        let mut lanes = view.as_standard_layout().into_owned();
+        parallel::process_batch(plan, lanes.as_slice_mut().expect("The array is in standard layout"));
+        view.assign(&lanes);
+        true
+    } else {
+        false
+    }
+}
+
+/// Copies the array into a complex valued array in standard layout
+fn to_complex<F, S, D>(x: &ArrayBase<S, D>) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+{
+    // mapv keeps the memory order of the input so the items are collected in
+    // logical order instead
+    let items = x.iter().map(|&v| v.into()).collect();
+    Array::from_shape_vec(x.raw_dim(), items).expect("The shape matches the number of items")
+}
+
+/// Transforms `x` along each of the `axes` in the given direction, each axis is
+/// scaled separately according to the normalization mode
+fn transform_axes<F, S, D>(
+    x: &ArrayBase<S, D>, 
+    axes: &[Axis], 
+    direction: Direction, 
+    norm: Normalization
+) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    let mut y = to_complex(x);
+    for &axis in axes {
+        process_axis(&mut y, axis, direction, norm);
+    }
+    y
+}
+
+/// The last two axes of an array with at least two dimensions
+fn last_two_axes(ndim: usize) -> [Axis; 2] {
+    assert!(ndim >= 2, "Two dimensional transforms require at least two axes, got {}", ndim);
+    [Axis(ndim - 2), Axis(ndim - 1)]
+}
+
+/// Computes the fast fourier transform of every lane of the array along `axis`
+pub fn fft_axis<F, S, D>(x: &ArrayBase<S, D>, axis: Axis) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    fft_axis_norm(x, axis, Normalization::Backward)
+}
+
+/// Computes the fast fourier transform of every lane of the array along `axis`
+/// with the given normalization
+pub fn fft_axis_norm<F, S, D>(x: &ArrayBase<S, D>, axis: Axis, norm: Normalization) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    transform_axes(x, &[axis], Direction::Forward, norm)
+}
+
+/// Computes the inverse fast fourier transform of every lane of the array along
+/// `axis`.
+/// The output *is* normalized
+pub fn ifft_axis<F, S, D>(x: &ArrayBase<S, D>, axis: Axis) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    ifft_axis_norm(x, axis, Normalization::Backward)
+}
+
+/// Computes the inverse fast fourier transform of every lane of the array along
+/// `axis` with the given normalization
+pub fn ifft_axis_norm<F, S, D>(x: &ArrayBase<S, D>, axis: Axis, norm: Normalization) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    transform_axes(x, &[axis], Direction::Inverse, norm)
+}
+
+/// Computes the two dimensional fast fourier transform over the last two axes
+/// of the array
+pub fn fft2<F, S, D>(x: &ArrayBase<S, D>) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    fft2_norm(x, Normalization::Backward)
+}
+
+/// Computes the two dimensional fast fourier transform over the last two axes
+/// of the array with the given normalization
+pub fn fft2_norm<F, S, D>(x: &ArrayBase<S, D>, norm: Normalization) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    transform_axes(x, &last_two_axes(x.ndim()), Direction::Forward, norm)
+}
+
+/// Computes the two dimensional inverse fast fourier transform over the last
+/// two axes of the array.
+/// The output *is* normalized
+pub fn ifft2<F, S, D>(x: &ArrayBase<S, D>) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    ifft2_norm(x, Normalization::Backward)
+}
+
+/// Computes the two dimensional inverse fast fourier transform over the last
+/// two axes of the array with the given normalization
+pub fn ifft2_norm<F, S, D>(x: &ArrayBase<S, D>, norm: Normalization) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    transform_axes(x, &last_two_axes(x.ndim()), Direction::Inverse, norm)
+}
+
+/// Computes the n-dimensional fast fourier transform over every axis of the
+/// array
+pub fn fftn<F, S, D>(x: &ArrayBase<S, D>) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    fftn_norm(x, Normalization::Backward)
+}
+
+/// Computes the n-dimensional fast fourier transform over every axis of the
+/// array with the given normalization
+pub fn fftn_norm<F, S, D>(x: &ArrayBase<S, D>, norm: Normalization) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    let axes: Vec<Axis> = (0..x.ndim()).map(Axis).collect();
+    transform_axes(x, &axes, Direction::Forward, norm)
+}
+
+/// Computes the n-dimensional inverse fast fourier transform over every axis
+/// of the array.
+/// The output *is* normalized
+pub fn ifftn<F, S, D>(x: &ArrayBase<S, D>) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    ifftn_norm(x, Normalization::Backward)
+}
+
+/// Computes the n-dimensional inverse fast fourier transform over every axis
+/// of the array with the given normalization
+pub fn ifftn_norm<F, S, D>(x: &ArrayBase<S, D>, norm: Normalization) -> Array<Complex<F>, D>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    let axes: Vec<Axis> = (0..x.ndim()).map(Axis).collect();
+    transform_axes(x, &axes, Direction::Inverse, norm)
+}
+
+/// Checks that the array has the axis
+fn check_axis(ndim: usize, axis: Axis) -> Result<(), RufftError> {
+    if axis.index() < ndim {
+        Ok(())
+    } else {
+        Err(RufftError::InvalidParameter("axis"))
+    }
+}
+
+/// Fallible version of `fft_axis`, returns an error if the array does not have
+/// the axis
+pub fn try_fft_axis<F, S, D>(x: &ArrayBase<S, D>, axis: Axis) -> Result<Array<Complex<F>, D>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    check_axis(x.ndim(), axis)?;
+    Ok(fft_axis(x, axis))
+}
+
+/// Fallible version of `ifft_axis`, returns an error if the array does not 
+/// have the axis
+pub fn try_ifft_axis<F, S, D>(x: &ArrayBase<S, D>, axis: Axis) -> Result<Array<Complex<F>, D>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    check_axis(x.ndim(), axis)?;
+    Ok(ifft_axis(x, axis))
+}
+
+/// Fallible version of `fft2`, returns an error if the array has fewer than 
+/// two dimensions
+pub fn try_fft2<F, S, D>(x: &ArrayBase<S, D>) -> Result<Array<Complex<F>, D>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    if x.ndim() < 2 {
+        return Err(RufftError::InvalidParameter("x"));
+    }
+    Ok(fft2(x))
+}
+
+/// Fallible version of `ifft2`, returns an error if the array has fewer than 
+/// two dimensions
+pub fn try_ifft2<F, S, D>(x: &ArrayBase<S, D>) -> Result<Array<Complex<F>, D>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data,
+    S::Elem: Copy + Into<Complex<F>>,
+    D: Dimension,
+    usize: AsPrimitive<F>,
+{
+    if x.ndim() < 2 {
+        return Err(RufftError::InvalidParameter("x"));
+    }
+    Ok(ifft2(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::prelude::*;
+    use crate::test_utils as test;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    fn assert_nearly_equal<D: Dimension>(a: &Array<Complex<f64>, D>, b: &Array<Complex<f64>, D>) {
+        assert_eq!(a.shape(), b.shape());
+        for (i, (&a, &b)) in a.iter().zip(b.iter()).enumerate() {
+            assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+    }
+
+    /// Direct evaluation of the two dimensional DFT
+    fn dft2(x: &Array2<f64>) -> Array2<Complex<f64>> {
+        let (rows, cols) = x.dim();
+        Array2::from_shape_fn((rows, cols), |(k, l)| {
+            x.indexed_iter().map(|((i, j), &v)| {
+                let phase = -core::f64::consts::TAU
+                    * ((k * i) as f64 / rows as f64 + (l * j) as f64 / cols as f64);
+                Complex::from_polar(v, phase)
+            }).sum()
+        })
+    }
+
+    fn image(rows: usize, cols: usize) -> Array2<f64> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| ((i * 7 + j * 3) as f64 * 0.37).sin())
+    }
+
+    #[test]
+    fn test_fft2_matches_dft2() {
+        let x = image(6, 10);
+        let output: Array2<Complex<f64>> = fft2(&x);
+        assert_nearly_equal(&output, &dft2(&x));
+    }
+
+    #[test]
+    fn test_fft2_view() {
+        // A strided view has lanes which aren't contiguous
+        let x = image(12, 20);
+        let view = x.slice(s![..;2, ..;2]);
+        let output: Array2<Complex<f64>> = fft2(&view);
+        assert_nearly_equal(&output, &dft2(&view.to_owned()));
+    }
+
+    #[test]
+    fn test_fft2_standard_layout() {
+        // The output is in standard layout whatever the layout of the input
+        let x = image(4, 6);
+        let column_major = x.t().as_standard_layout().into_owned().reversed_axes();
+        assert!(!column_major.is_standard_layout());
+        let output: Array2<Complex<f64>> = fft2(&column_major);
+        assert!(output.is_standard_layout());
+        assert_nearly_equal(&output, &dft2(&x));
+    }
+
+    #[test]
+    fn test_fft_axis() {
+        let x = image(8, 5);
+        let output: Array2<Complex<f64>> = fft_axis(&x, Axis(0));
+        for (j, column) in x.columns().into_iter().enumerate() {
+            let reference: Vec<Complex<f64>> = crate::fft::dft(&column.to_vec());
+            for (i, &b) in reference.iter().enumerate() {
+                let a = output[[i, j]];
+                assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fftn_roundtrip() {
+        let x = Array3::from_shape_fn((4, 5, 7), |(i, j, k)| {
+            Complex::new(((i + 2 * j + 3 * k) as f64).sin(), (i * j * k) as f64 * 0.1)
+        });
+        let spectrum: Array3<Complex<f64>> = fftn(&x);
+        let y: Array3<Complex<f64>> = ifftn(&spectrum);
+        assert_nearly_equal(&y, &x);
+    }
+
+    #[test]
+    fn test_ifft2_roundtrip() {
+        let x = image(16, 9);
+        let spectrum: Array2<Complex<f64>> = fft2(&x);
+        let y: Array2<Complex<f64>> = ifft2(&spectrum);
+        assert_nearly_equal(&y, &x.mapv(|v| Complex::new(v, 0.0)));
+    }
+
+    #[test]
+    fn test_fft2_ortho() {
+        // The scaling of each axis combines to 1/sqrt(rows * cols)
+        let x = image(6, 10);
+        let output: Array2<Complex<f64>> = fft2_norm(&x, Normalization::Ortho);
+        assert_nearly_equal(&output, &dft2(&x).mapv(|v| v / 60f64.sqrt()));
+        let y: Array2<Complex<f64>> = ifft2_norm(&output, Normalization::Ortho);
+        assert_nearly_equal(&y, &x.mapv(|v| Complex::new(v, 0.0)));
+    }
+
+    #[test]
+    fn test_try_errors() {
+        let x = Array1::<f64>::ones(8);
+        assert_eq!(try_fft2::<f64, _, _>(&x), Err(RufftError::InvalidParameter("x")));
+        assert_eq!(try_ifft2::<f64, _, _>(&x), Err(RufftError::InvalidParameter("x")));
+        assert_eq!(try_fft_axis::<f64, _, _>(&x, Axis(1)), Err(RufftError::InvalidParameter("axis")));
+        assert_eq!(try_ifft_axis::<f64, _, _>(&x, Axis(1)), Err(RufftError::InvalidParameter("axis")));
+        assert!(try_fft_axis::<f64, _, _>(&x, Axis(0)).is_ok());
+    }
+}
@@ -0,0 +1,229 @@
+//! Multithreaded transforms using rayon, enabled by the `rayon` feature. The
+//! halves of long radix-2 transforms, the two independent transforms of
+//! Bluestein's algorithm and the frames of batched transforms are computed on
+//! the rayon thread pool.
+//!
+//! Sharing data between threads requires `Send` and `Sync` which the generic
+//! float type of the transforms does not guarantee, so only `f32` and `f64`
+//! transforms are parallelized and other float types are computed on the
+//! calling thread.
+use core::any::{ Any, TypeId };
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use rayon::prelude::*;
+use crate::fft::ct;
+use crate::fft::plan::Plan;
+
+/// Transform lengths above which the halves of a radix-2 transform are
+/// computed on separate threads
+pub(crate) const PARALLEL_LEN: usize = 1 << 14;
+
+/// Number of butterflies of a parallel stage computed by each task
+const CHUNK: usize = 1 << 12;
+
+/// Reinterprets the slice as a slice of `Complex<T>` if `F` is `T`
+fn cast<F: 'static, T: 'static>(x: &mut [Complex<F>]) -> Option<&mut [Complex<T>]> {
+    if TypeId::of::<F>() == TypeId::of::<T>() {
+        // SAFETY: F and T are the same type
+        Some(unsafe { &mut *(x as *mut [Complex<F>] as *mut [Complex<T>]) })
+    } else {
+        None
+    }
+}
+
+/// Reinterprets the slice as a slice of `Complex<T>` if `F` is `T`
+fn cast_ref<F: 'static, T: 'static>(x: &[Complex<F>]) -> Option<&[Complex<T>]> {
+    if TypeId::of::<F>() == TypeId::of::<T>() {
+        // SAFETY: F and T are the same type
+        Some(unsafe { &*(x as *const [Complex<F>] as *const [Complex<T>]) })
+    } else {
+        None
+    }
+}
+
+/// Computes the stages of the bit reversal permuted slice. The first stages of
+/// each half are independent so the halves are computed recursively on
+/// separate threads, followed by the butterflies of the final stage which are
+/// split into chunks
+fn halves<F>(x: &mut [Complex<F>], twiddles: &[Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + Send + Sync + 'static,
+{
+    let n = x.len();
+    if n <= PARALLEL_LEN {
+        let mut len = 2;
+        while len <= n {
+            ct::complex::stage(x, twiddles, len);
+            len <<= 1;
+        }
+        return;
+    }
+    let (lo, hi) = x.split_at_mut(n / 2);
+    rayon::join(|| halves(lo, twiddles), || halves(hi, twiddles));
+    let stride = 2 * twiddles.len() / n;
+    lo.par_chunks_mut(CHUNK)
+        .zip(hi.par_chunks_mut(CHUNK))
+        .enumerate()
+        .for_each(|(c, (lo, hi))| {
+            for (j, (even, odd)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let tmp = *odd * twiddles[(c * CHUNK + j) * stride];
+                *odd = *even - tmp;
+                *even += tmp;
+            }
+        });
+}
+
+/// Computes the radix-2 transform with the precomputed twiddle factors on the
+/// thread pool. Returns false if the transform was not computed because it is
+/// too short or the float type is not `f32` or `f64`
+pub(crate) fn fft_with_twiddles<F: 'static>(x: &mut [Complex<F>], twiddles: &[Complex<F>]) -> bool {
+    if x.len() <= PARALLEL_LEN {
+        return false;
+    }
+    if let (Some(x), Some(twiddles)) = (cast::<F, f64>(x), cast_ref::<F, f64>(twiddles)) {
+        ct::complex::bit_reverse_permute(x);
+        halves(x, twiddles);
+        true
+    } else if let (Some(x), Some(twiddles)) = (cast::<F, f32>(x), cast_ref::<F, f32>(twiddles)) {
+        ct::complex::bit_reverse_permute(x);
+        halves(x, twiddles);
+        true
+    } else {
+        false
+    }
+}
+
+/// Computes the radix-2 transforms of both slices in-place on separate threads.
+/// Returns false if the float type is not `f32` or `f64`
+pub(crate) fn fft_in_place_pair<F: 'static>(a: &mut [Complex<F>], b: &mut [Complex<F>]) -> bool {
+    if let (Some(a), Some(b)) = (cast::<F, f64>(a), cast::<F, f64>(b)) {
+        rayon::join(|| ct::complex::fft_in_place(a), || ct::complex::fft_in_place(b));
+        true
+    } else if let (Some(a), Some(b)) = (cast::<F, f32>(a), cast::<F, f32>(b)) {
+        rayon::join(|| ct::complex::fft_in_place(a), || ct::complex::fft_in_place(b));
+        true
+    } else {
+        false
+    }
+}
+
+/// Transforms the frames with clones of the plan on the thread pool
+fn frames<F>(plan: &Plan<F>, buf: &mut [Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + Send + Sync + 'static,
+    usize: AsPrimitive<F>,
+{
+    buf.par_chunks_mut(plan.len())
+        .for_each_with(plan.clone(), |plan, frame| plan.process(frame));
+}
+
+/// Transforms each consecutive frame of the plan length of `buf` in-place on
+/// the thread pool. Returns false if the float type is not `f32` or `f64`
+pub(crate) fn process_batch<F: 'static>(plan: &mut Plan<F>, buf: &mut [Complex<F>]) -> bool {
+    let plan = plan as &mut dyn Any;
+    if let Some(plan) = plan.downcast_mut::<Plan<f64>>() {
+        frames(plan, cast::<F, f64>(buf).expect("The plan and buffer have the same float type"));
+        true
+    } else if let Some(plan) = plan.downcast_mut::<Plan<f32>>() {
+        frames(plan, cast::<F, f32>(buf).expect("The plan and buffer have the same float type"));
+        true
+    } else {
+        false
+    }
+}
+
+/// Transforms the lanes with clones of the plan on the thread pool
+fn lanes<F>(plan: &Plan<F>, lanes: Vec<&mut [Complex<F>]>)
+where
+    F: Float + FloatConst + NumAssign + Send + Sync + 'static,
+    usize: AsPrimitive<F>,
+{
+    lanes.into_par_iter()
+        .for_each_with(plan.clone(), |plan, lane| plan.process(lane));
+}
+
+/// Transforms each lane of the plan length in-place on the thread pool. Unlike
+/// `process_batch` the lanes don't need to be consecutive. Returns false if the
+/// float type is not `f32` or `f64`
+pub(crate) fn process_lanes<F: 'static>(plan: &mut Plan<F>, x: Vec<&mut [Complex<F>]>) -> bool {
+    let plan = plan as &mut dyn Any;
+    if let Some(plan) = plan.downcast_mut::<Plan<f64>>() {
+        let x = x.into_iter()
+            .map(|lane| cast::<F, f64>(lane).expect("The plan and lanes have the same float type"))
+            .collect();
+        lanes(plan, x);
+        true
+    } else if let Some(plan) = plan.downcast_mut::<Plan<f32>>() {
+        let x = x.into_iter()
+            .map(|lane| cast::<F, f32>(lane).expect("The plan and lanes have the same float type"))
+            .collect();
+        lanes(plan, x);
+        true
+    } else {
+        false
+    }
+}
+
+/// Returns true if transforms of the float type are computed on the thread pool
+pub(crate) fn is_parallel<F: 'static>() -> bool {
+    TypeId::of::<F>() == TypeId::of::<f64>() || TypeId::of::<F>() == TypeId::of::<f32>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::plan::Direction;
+    use crate::test_utils::{ assert_nearly_equal, signal };
+
+    /// Computes the radix-2 transform on the calling thread
+    fn sequential(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = x.len();
+        let twiddles: Vec<Complex<f64>> = ct::complex::twiddles(n).collect();
+        let mut y = x.to_vec();
+        ct::complex::bit_reverse_permute(&mut y);
+        let mut len = 2;
+        while len <= n {
+            ct::complex::stage(&mut y, &twiddles, len);
+            len <<= 1;
+        }
+        y
+    }
+
+    #[test]
+    fn test_fft_with_twiddles() {
+        for n in [PARALLEL_LEN, 2 * PARALLEL_LEN, 1 << 18] {
+            let x = signal(n);
+            let twiddles: Vec<Complex<f64>> = ct::complex::twiddles(n).collect();
+            let mut y = x.clone();
+            assert_eq!(fft_with_twiddles(&mut y, &twiddles), n > PARALLEL_LEN);
+            if n > PARALLEL_LEN {
+                assert_nearly_equal(&y, &sequential(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_in_place_pair() {
+        let a = signal(1024);
+        let b = signal(2048);
+        let (mut y_a, mut y_b) = (a.clone(), b.clone());
+        assert!(fft_in_place_pair(&mut y_a, &mut y_b));
+        assert_nearly_equal(&y_a, &sequential(&a));
+        assert_nearly_equal(&y_b, &sequential(&b));
+    }
+
+    #[test]
+    fn test_process_batch() {
+        for (len, frames) in [(1, 7), (64, 100), (100, 33), (127, 10)] {
+            let x = signal(len * frames);
+            let mut plan = Plan::new(len, Direction::Forward);
+            let mut y = x.clone();
+            assert!(process_batch(&mut plan, &mut y));
+            for (frame, output) in x.chunks(len).zip(y.chunks(len)) {
+                let mut expected = frame.to_vec();
+                plan.process(&mut expected);
+                assert_nearly_equal(output, &expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+pub mod complex;
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::traits::{ Iterable, IterableMut, Signal };
+
+pub use complex::coprime_factors;
+
+/// Computes the fast fourier transform of the real valued input collection with
+/// the Good-Thomas prime factor algorithm. The length must have at least two 
+/// coprime factors, see `coprime_factors`
+pub fn fft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = F::zero();
+    let y: C = x.samples()
+        .map(|&x| Complex::new(x, zero))
+        .collect();
+    complex::fft(&y)
+}
+
+/// Computes the inverse fast fourier transform of the complex input collection
+/// with the Good-Thomas prime factor algorithm and returns the real valued 
+/// output collection
+/// The output *is* normalized.
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{   
+    let y: I = complex::ifft(x);
+    y.iter().map(|x| x.re).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length has no coprime factors
+pub fn try_fft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length has no coprime factors
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    complex::check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils as test;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    #[test]
+    fn test_fft_pfa_real_roundtrip() {
+        let x: Vec<f64> = (0..143).map(|i| (i as f64 * 0.4).sin()).collect();
+        let spectrum: Vec<Complex<f64>> = fft(&x);
+        let reference: Vec<Complex<f64>> = crate::fft::dft(&x);
+        for (i, (&a, &b)) in spectrum.iter().zip(reference.iter()).enumerate() {
+            assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64),
+                "{} => {} != {}", i, a, b);
+        }
+        let y: Vec<f64> = ifft(&spectrum);
+        for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+            assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_try_fft_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&empty), Err(RufftError::EmptyInput));
+        let x: Vec<f64> = vec![1.0; 121];
+        assert_eq!(try_fft::<f64, _, Vec<Complex<f64>>>(&x), Err(RufftError::UnsupportedLength(121)));
+        let x: Vec<Complex<f64>> = vec![Complex::new(1.0, 0.0); 121];
+        assert_eq!(complex::try_fft(&x), Err(RufftError::UnsupportedLength(121)));
+        assert_eq!(complex::try_ifft::<f64, _, Vec<Complex<f64>>>(&x), Err(RufftError::UnsupportedLength(121)));
+        let x: Vec<f64> = vec![1.0; 143];
+        let spectrum: Vec<Complex<f64>> = try_fft(&x).unwrap();
+        assert!(try_ifft::<f64, _, Vec<f64>>(&spectrum).is_ok());
+    }
+}
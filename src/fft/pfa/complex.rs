@@ -0,0 +1,235 @@
+use num_complex::Complex;
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::plan::Plan;
+use crate::traits::{ Iterable, IterableMut };
+
+/// Splits `n` into two coprime factors `(n1, n2)` with `n1 * n2 = n` and both
+/// greater than one. The factor `n1` is the part of `n` which factors into 2, 3,
+/// 5 and 7 when possible, otherwise the power of the smallest prime factor of
+/// `n`. Returns `None` if `n` is a prime power
+pub fn coprime_factors(n: usize) -> Option<(usize, usize)> {
+    if n < 6 {
+        return None;
+    }
+    let mut smooth = 1;
+    let mut rest = n;
+    for p in [2, 3, 5, 7] {
+        while Integer::is_multiple_of(&rest, &p) {
+            smooth *= p;
+            rest /= p;
+        }
+    }
+    if smooth > 1 && rest > 1 {
+        return Some((smooth, rest));
+    }
+
+    let mut p = 2;
+    while !Integer::is_multiple_of(&n, &p) {
+        p += 1;
+    }
+    let mut n1 = 1;
+    let mut n2 = n;
+    while Integer::is_multiple_of(&n2, &p) {
+        n1 *= p;
+        n2 /= p;
+    }
+    if n2 > 1 { Some((n1, n2)) } else { None }
+}
+
+/// Checks that the length has coprime factors, as required by the Good-Thomas
+/// algorithm
+pub(crate) fn check_len(n: usize) -> Result<(), RufftError> {
+    if n == 0 {
+        Err(RufftError::EmptyInput)
+    } else if coprime_factors(n).is_none() {
+        Err(RufftError::UnsupportedLength(n))
+    } else {
+        Ok(())
+    }
+}
+
+/// Computes the inverse of `a` modulo `m` with the extended euclidean 
+/// algorithm. `a` and `m` must be coprime
+fn mod_inverse(a: usize, m: usize) -> usize {
+    let (mut old_r, mut r) = (a as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    debug_assert_eq!(old_r, 1);
+    old_s.rem_euclid(m as i128) as usize
+}
+
+/// Precomputed state for the Good-Thomas prime factor algorithm which maps a
+/// length `n1 * n2` DFT with coprime `n1` and `n2` onto a two dimensional 
+/// `n1` by `n2` DFT using the chinese remainder theorem. Unlike the cooley-tukey
+/// decomposition no twiddle factors are needed between the two dimensions
+#[derive(Debug, Clone)]
+pub(crate) struct GoodThomas<F> {
+    n1: usize,
+    n2: usize,
+    row_plan: Box<Plan<F>>,
+    col_plan: Box<Plan<F>>,
+    input_index: Vec<usize>,
+    output_index: Vec<usize>,
+    scratch: Vec<Complex<F>>,
+}
+
+impl<F> GoodThomas<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{
+    pub(crate) fn new(n1: usize, n2: usize) -> Self {
+        debug_assert_eq!(n1.gcd(&n2), 1);
+        let n = n1 * n2;
+        // Ruritanian mapping of the input, j = (j1 * n2 + j2 * n1) mod n
+        let input_index = (0..n1)
+            .flat_map(|j1| (0..n2).map(move |j2| (j1 * n2 + j2 * n1) % n))
+            .collect();
+        // Chinese remainder theorem mapping of the output, k = k1 mod n1 and 
+        // k = k2 mod n2, stored transposed as the output of the column transforms
+        let e1 = n2 * mod_inverse(n2 % n1, n1);
+        let e2 = n1 * mod_inverse(n1 % n2, n2);
+        let output_index = (0..n2)
+            .flat_map(|k2| (0..n1).map(move |k1| (k1 * e1 + k2 * e2) % n))
+            .collect();
+        Self {
+            n1,
+            n2,
+            row_plan: Box::new(Plan::forward(n2)),
+            col_plan: Box::new(Plan::forward(n1)),
+            input_index,
+            output_index,
+            scratch: vec![Complex::new(F::zero(), F::zero()); n],
+        }
+    }
+
+    pub(crate) fn process(&mut self, x: &mut [Complex<F>]) {
+        let (n1, n2) = (self.n1, self.n2);
+        self.scratch.iter_mut()
+            .zip(self.input_index.iter())
+            .for_each(|(s, &j)| *s = x[j]);
+        for row in self.scratch.chunks_exact_mut(n2) {
+            self.row_plan.process(row);
+        }
+        // Transpose so that the columns are contiguous
+        for j1 in 0..n1 {
+            for j2 in 0..n2 {
+                x[j2 * n1 + j1] = self.scratch[j1 * n2 + j2];
+            }
+        }
+        for col in x.chunks_exact_mut(n1) {
+            self.col_plan.process(col);
+        }
+        self.scratch.copy_from_slice(x);
+        self.output_index.iter()
+            .zip(self.scratch.iter())
+            .for_each(|(&k, &s)| x[k] = s);
+    }
+}
+
+/// Computes the fast fourier transform of the complex valued input collection
+/// with the Good-Thomas prime factor algorithm and outputs a complex valued 
+/// collection. The length must have at least two coprime factors, see 
+/// `coprime_factors`
+pub fn fft<F, I>(x: &I) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let (n1, n2) = coprime_factors(n)
+        .unwrap_or_else(|| panic!("The prime factor algorithm requires coprime factors, got a length of {}", n));
+    let mut y = x.clone();
+    GoodThomas::new(n1, n2).process(y.as_mut_slice());
+    y
+}
+
+/// Computes the inverse fast fourier transform of the complex valued input 
+/// collection with the Good-Thomas prime factor algorithm and outputs a complex
+/// valued collection. 
+/// The output *is* normalized
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    // The inverse transform is computed as conj(fft(conj(x))) / n
+    let n_f: F = x.len().as_();
+    let conj: I = x.iter().map(|v| v.conj()).collect();
+    fft(&conj).iter().map(|v| v.conj() / n_f).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length has no coprime factors
+pub fn try_fft<F, I>(x: &I) -> Result<I, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length has no coprime factors
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ assert_nearly_equal, signal };
+
+    #[test]
+    fn test_coprime_factors() {
+        assert_eq!(coprime_factors(15), Some((3, 5)));
+        assert_eq!(coprime_factors(63), Some((9, 7)));
+        assert_eq!(coprime_factors(1001), Some((7, 143)));
+        assert_eq!(coprime_factors(143), Some((11, 13)));
+        assert_eq!(coprime_factors(2 * 23), Some((2, 23)));
+        assert_eq!(coprime_factors(4 * 1009), Some((4, 1009)));
+        assert_eq!(coprime_factors(12 * 1009), Some((12, 1009)));
+        assert_eq!(coprime_factors(121), None);
+        assert_eq!(coprime_factors(1024), None);
+        assert_eq!(coprime_factors(13), None);
+    }
+
+    #[test]
+    fn test_fft_pfa_matches_dft() {
+        for n in [6, 15, 63, 46, 143, 1001] {
+            let x = signal(n);
+            let output = fft(&x);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+            assert_nearly_equal(&output, &reference);
+        }
+    }
+
+    #[test]
+    fn test_ifft_pfa_roundtrip() {
+        let x = signal(1001);
+        let y: Vec<Complex<f64>> = ifft(&fft(&x));
+        assert_nearly_equal(&y, &x);
+    }
+}
@@ -0,0 +1,389 @@
+//! Reusable FFT plans for transforming many collections of the same length.
+//! A `Plan` precomputes everything which only depends on the length and the
+//! direction of the transform, such as twiddle factors, Bluestein chirp spectra
+//! and scratch space, so that repeated calls to `Plan::process` only do the
+//! work of the transform itself. The functions of the algorithm modules build
+//! this state again on each call.
+//!
+//! ```
+//! use rufft::{Complex, fft::plan::{FftPlanner, Direction}};
+//!
+//! let mut planner = FftPlanner::<f64>::new();
+//! let plan = planner.plan(1000, Direction::Forward);
+//! let mut frame = vec![Complex::new(1.0, 0.0); 1000];
+//! plan.process(&mut frame);
+//! ```
+use std::collections::HashMap;
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use super::{ ct, czt, mixed, pfa, rader, scale, Normalization };
+use crate::error::RufftError;
+
+/// The direction of the transform computed by a `Plan`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Forward,
+    Inverse,
+}
+
+/// Algorithm specific precomputed state
+#[derive(Debug, Clone)]
+enum Algorithm<F> {
+    /// Lengths of zero and one where the transform is the identity
+    Identity,
+    Radix2 { twiddles: Vec<Complex<F>> },
+    MixedRadix { twiddles: Vec<Complex<F>>, scratch: Vec<Complex<F>> },
+    Rader(rader::complex::Rader<F>),
+    GoodThomas(pfa::complex::GoodThomas<F>),
+    Bluestein(czt::Bluestein<F>),
+}
+
+impl<F> Algorithm<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{
+    fn new(len: usize) -> Self {
+        // Products of two coprime butterfly radices, e.g. 15 = 3 * 5, are split
+        // by the Good-Thomas algorithm which needs no twiddle factors between 
+        // the two butterflies
+        let butterflies = pfa::coprime_factors(len)
+            .filter(|&(n1, n2)| mixed::complex::is_butterfly_len(n1) && mixed::complex::is_butterfly_len(n2));
+        if len <= 1 {
+            Self::Identity
+        } else if len.is_power_of_two() {
+            Self::Radix2 { twiddles: ct::complex::twiddles(len).collect() }
+        } else if let Some((n1, n2)) = butterflies {
+            Self::GoodThomas(pfa::complex::GoodThomas::new(n1, n2))
+        } else if mixed::is_supported_len(len) {
+            Self::MixedRadix { 
+                twiddles: mixed::complex::twiddles(len).collect(), 
+                scratch: vec![Complex::new(F::zero(), F::zero()); len],
+            }
+        } else if rader::is_prime(len) {
+            Self::Rader(rader::complex::Rader::new(len))
+        } else if let Some((n1, n2)) = pfa::coprime_factors(len) {
+            Self::GoodThomas(pfa::complex::GoodThomas::new(n1, n2))
+        } else {
+            Self::Bluestein(czt::Bluestein::new(len))
+        }
+    }
+
+    /// Computes the unnormalized forward transform in-place
+    fn forward(&mut self, buf: &mut [Complex<F>]) {
+        match self {
+            Self::Identity => {},
+            Self::Radix2 { twiddles } => ct::complex::fft_with_twiddles(buf, twiddles),
+            Self::MixedRadix { twiddles, scratch } => {
+                scratch.copy_from_slice(buf);
+                mixed::complex::process(scratch, buf, 1, twiddles);
+            },
+            Self::Rader(rader) => rader.process(buf),
+            Self::GoodThomas(good_thomas) => good_thomas.process(buf),
+            Self::Bluestein(bluestein) => bluestein.process(buf),
+        }
+    }
+}
+
+/// A precomputed fast fourier transform of a fixed length and direction. The
+/// algorithm is chosen based on the length, radix-2 cooley-tukey for powers of
+/// two, mixed-radix cooley-tukey for lengths which factor into 2, 3, 5 and 7, 
+/// Rader's algorithm for other prime lengths, the Good-Thomas prime factor 
+/// algorithm for lengths with coprime factors, including products of two 
+/// coprime butterfly radices such as 15 and 28, and Bluestein's chirp-z algorithm
+/// otherwise i.e. for powers of primes larger than 7. The sub-transforms of the
+/// Good-Thomas and Rader plans are planned in the same way, so a length such as
+/// `12 * 1009` runs the mixed-radix kernel on the rows and Rader's algorithm on
+/// the prime columns
+#[derive(Debug, Clone)]
+pub struct Plan<F> {
+    len: usize,
+    direction: Direction,
+    norm: Normalization,
+    algorithm: Algorithm<F>,
+}
+
+impl<F> Plan<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{
+    /// Create a plan for transforms of length `len` in the given direction
+    pub fn new(len: usize, direction: Direction) -> Self {
+        Self::with_normalization(len, direction, Normalization::Backward)
+    }
+
+    /// Create a plan for transforms of length `len` in the given direction
+    /// which are scaled according to the normalization mode
+    pub fn with_normalization(len: usize, direction: Direction, norm: Normalization) -> Self {
+        Self { len, direction, norm, algorithm: Algorithm::new(len) }
+    }
+
+    /// Create a plan for forward transforms of length `len`
+    pub fn forward(len: usize) -> Self {
+        Self::new(len, Direction::Forward)
+    }
+
+    /// Create a plan for inverse transforms of length `len`
+    pub fn inverse(len: usize) -> Self {
+        Self::new(len, Direction::Inverse)
+    }
+
+    /// The length of the transforms computed by the plan
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the plan is for zero length transforms
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The direction of the transforms computed by the plan
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// The normalization mode of the transforms computed by the plan
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Computes the transform of `buf` in-place, scaled according to the 
+    /// normalization mode of the plan. With the default backward normalization
+    /// the inverse transform output *is* normalized. Panics if the length of 
+    /// `buf` is not the length of the plan
+    pub fn process(&mut self, buf: &mut [Complex<F>]) {
+        assert_eq!(buf.len(), self.len, "Buffer length does not match the plan length");
+        match self.direction {
+            Direction::Forward => {
+                self.algorithm.forward(buf);
+                scale(buf, self.norm.forward_scale(self.len));
+            },
+            Direction::Inverse => {
+                // The inverse transform is computed as conj(fft(conj(x))) / n
+                buf.iter_mut().for_each(|v| *v = v.conj());
+                self.algorithm.forward(buf);
+                let factor: F = self.norm.inverse_scale(self.len);
+                buf.iter_mut().for_each(|v| *v = v.conj().scale(factor));
+            }
+        }
+    }
+
+    /// Fallible version of `process`, returns an error rather than panicking 
+    /// if the length of `buf` is not the length of the plan
+    pub fn try_process(&mut self, buf: &mut [Complex<F>]) -> Result<(), RufftError> {
+        if buf.len() != self.len {
+            return Err(RufftError::LengthMismatch { expected: self.len, actual: buf.len() });
+        }
+        self.process(buf);
+        Ok(())
+    }
+
+    /// Computes the transform of each consecutive frame of the plan length in
+    /// `buf` in-place. With the `rayon` feature the frames are transformed on
+    /// the rayon thread pool. Panics if the length of `buf` is not a multiple
+    /// of the length of the plan
+    pub fn process_batch(&mut self, buf: &mut [Complex<F>]) {
+        if self.len == 0 {
+            assert!(buf.is_empty(), "Buffer length is not a multiple of the plan length");
+            return;
+        }
+        assert_eq!(buf.len() % self.len, 0, "Buffer length is not a multiple of the plan length");
+        #[cfg(feature = "rayon")]
+        if crate::fft::parallel::process_batch(self, buf) {
+            return;
+        }
+        buf.chunks_exact_mut(self.len).for_each(|frame| self.process(frame));
+    }
+
+    /// Fallible version of `process_batch`, returns an error rather than 
+    /// panicking if the length of `buf` is not a multiple of the length of the
+    /// plan. The expected length of the error is the next multiple
+    pub fn try_process_batch(&mut self, buf: &mut [Complex<F>]) -> Result<(), RufftError> {
+        let expected = if self.len == 0 { 0 } else { buf.len().next_multiple_of(self.len) };
+        if buf.len() != expected {
+            return Err(RufftError::LengthMismatch { expected, actual: buf.len() });
+        }
+        self.process_batch(buf);
+        Ok(())
+    }
+}
+
+/// Creates and caches plans so that each length, direction and normalization 
+/// is only planned once
+#[derive(Debug, Clone, Default)]
+pub struct FftPlanner<F> {
+    plans: HashMap<(usize, Direction, Normalization), Plan<F>>,
+}
+
+impl<F> FftPlanner<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{
+    /// Create a planner with no cached plans
+    pub fn new() -> Self {
+        Self { plans: HashMap::new() }
+    }
+
+    /// Returns the cached plan for the length and direction, creating it if
+    /// it does not exist yet
+    pub fn plan(&mut self, len: usize, direction: Direction) -> &mut Plan<F> {
+        self.plan_with_normalization(len, direction, Normalization::Backward)
+    }
+
+    /// Returns the cached plan for the length, direction and normalization, 
+    /// creating it if it does not exist yet
+    pub fn plan_with_normalization(
+        &mut self, 
+        len: usize, 
+        direction: Direction, 
+        norm: Normalization
+    ) -> &mut Plan<F> {
+        self.plans
+            .entry((len, direction, norm))
+            .or_insert_with(|| Plan::with_normalization(len, direction, norm))
+    }
+
+    /// Returns the cached forward plan for the length
+    pub fn plan_forward(&mut self, len: usize) -> &mut Plan<F> {
+        self.plan(len, Direction::Forward)
+    }
+
+    /// Returns the cached inverse plan for the length
+    pub fn plan_inverse(&mut self, len: usize) -> &mut Plan<F> {
+        self.plan(len, Direction::Inverse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ assert_nearly_equal, read_json, signal, Data, Json };
+
+    #[test]
+    fn test_plan_radix2_dataset() {
+        let json_data: Json<f64> = read_json("datasets/fft/fft/fft.json");
+        let mut buf: Vec<Complex<f64>> = match json_data.input_data {
+            Data::Array(input) => input.iter().map(|&x| Complex::new(x, 0.0)).collect(),
+            _ => panic!("Read the input data incorrectly")
+        };
+        let reference: Vec<Complex<f64>> = match json_data.output_data {
+            Data::ComplexVals { mag, phase } => mag.iter()
+                .zip(phase.iter())
+                .map(|(&m, &p)| Complex::from_polar(m, p))
+                .collect(),
+            _ => panic!("Read the output data incorrectly")
+        };
+        let mut plan = Plan::forward(buf.len());
+        plan.process(&mut buf);
+        assert_nearly_equal(&buf, &reference);
+    }
+
+    #[test]
+    fn test_plan_process_batch() {
+        for (len, frames) in [(0, 0), (1, 5), (16, 8), (30, 4), (97, 3)] {
+            let x = signal(len * frames);
+            let mut plan = Plan::inverse(len);
+            let mut y = x.clone();
+            plan.process_batch(&mut y);
+            for (frame, output) in x.chunks(len.max(1)).zip(y.chunks(len.max(1))) {
+                let mut expected = frame.to_vec();
+                plan.process(&mut expected);
+                assert_nearly_equal(output, &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_try_process_batch() {
+        let mut plan = Plan::<f64>::forward(8);
+        let mut buf = signal(20);
+        assert_eq!(
+            plan.try_process_batch(&mut buf), 
+            Err(RufftError::LengthMismatch { expected: 24, actual: 20 })
+        );
+        assert_eq!(plan.try_process_batch(&mut buf[..16]), Ok(()));
+    }
+
+    #[test]
+    fn test_plan_matches_dft() {
+        let mut planner = FftPlanner::<f64>::new();
+        for n in [1, 2, 3, 12, 15, 16, 28, 63, 100, 121, 127, 1000, 1001, 1009, 2 * 1009] {
+            let x = signal(n);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+            let plan = planner.plan_forward(n);
+            // Process twice to check that the scratch space is reset
+            for _ in 0..2 {
+                let mut buf = x.clone();
+                plan.process(&mut buf);
+                assert_nearly_equal(&buf, &reference);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_algorithm() {
+        let good_thomas = |n| matches!(Plan::<f64>::forward(n).algorithm, Algorithm::GoodThomas(_));
+        assert!(good_thomas(1001));
+        assert!(good_thomas(15));
+        assert!(good_thomas(28));
+        // 9 is not a single butterfly so 63 = 9 * 7 uses the mixed-radix kernel
+        assert!(matches!(Plan::<f64>::forward(63).algorithm, Algorithm::MixedRadix { .. }));
+        assert!(matches!(Plan::<f64>::forward(1009).algorithm, Algorithm::Rader(_)));
+    }
+
+    #[test]
+    fn test_plan_large_prime_factor() {
+        for n in [4 * 1009, 12 * 1009] {
+            let x = signal(n);
+            let mut plan = Plan::forward(n);
+            assert!(matches!(plan.algorithm, Algorithm::GoodThomas(_)), "n = {}", n);
+            let mut buf = x.clone();
+            plan.process(&mut buf);
+            let mut reference = x.clone();
+            czt::Bluestein::new(n).process(&mut reference);
+            assert_nearly_equal(&buf, &reference);
+        }
+    }
+
+    #[test]
+    fn test_plan_roundtrip() {
+        let mut planner = FftPlanner::<f64>::new();
+        for n in [8, 100, 1024, 1000] {
+            let x = signal(n);
+            let mut buf = x.clone();
+            planner.plan_forward(n).process(&mut buf);
+            planner.plan_inverse(n).process(&mut buf);
+            assert_nearly_equal(&buf, &x);
+        }
+    }
+
+    #[test]
+    fn test_plan_try_process_length_mismatch() {
+        let mut plan = Plan::<f64>::forward(16);
+        let mut buf = signal(15);
+        assert_eq!(plan.try_process(&mut buf), Err(RufftError::LengthMismatch { expected: 16, actual: 15 }));
+        let mut buf = signal(16);
+        assert_eq!(plan.try_process(&mut buf), Ok(()));
+    }
+
+    #[test]
+    fn test_plan_normalization() {
+        let mut planner = FftPlanner::<f64>::new();
+        for n in [8, 100, 127] {
+            let x = signal(n);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+            for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+                let factor: f64 = norm.forward_scale(n);
+                let scaled: Vec<Complex<f64>> = reference.iter().map(|v| v * factor).collect();
+                let mut buf = x.clone();
+                planner.plan_with_normalization(n, Direction::Forward, norm).process(&mut buf);
+                assert_nearly_equal(&buf, &scaled);
+                planner.plan_with_normalization(n, Direction::Inverse, norm).process(&mut buf);
+                assert_nearly_equal(&buf, &x);
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use num_complex::Complex;
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::plan::Plan;
+use crate::traits::{ Iterable, IterableMut };
+
+/// Returns true if `n` is a prime number
+pub fn is_prime(n: usize) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut d = 2;
+    while d * d <= n {
+        if Integer::is_multiple_of(&n, &d) {
+            return false;
+        }
+        d += 1;
+    }
+    true
+}
+
+/// Checks that the length is prime, as required by Rader's algorithm
+pub(crate) fn check_len(n: usize) -> Result<(), RufftError> {
+    if n == 0 {
+        Err(RufftError::EmptyInput)
+    } else if !is_prime(n) {
+        Err(RufftError::UnsupportedLength(n))
+    } else {
+        Ok(())
+    }
+}
+
+/// Computes `base^exp mod modulus` without overflowing
+fn pow_mod(base: usize, mut exp: usize, modulus: usize) -> usize {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as usize
+}
+
+/// Returns the smallest primitive root of the prime `p` i.e. the generator of 
+/// the multiplicative group of integers modulo `p`
+fn primitive_root(p: usize) -> usize {
+    let order = p - 1;
+    let mut factors = Vec::new();
+    let mut m = order;
+    let mut d = 2;
+    while d * d <= m {
+        if Integer::is_multiple_of(&m, &d) {
+            factors.push(d);
+            while Integer::is_multiple_of(&m, &d) {
+                m /= d;
+            }
+        }
+        d += 1;
+    }
+    if m > 1 {
+        factors.push(m);
+    }
+    (1..p)
+        .find(|&g| factors.iter().all(|&q| pow_mod(g, order / q, p) != 1))
+        .expect("Every prime has a primitive root")
+}
+
+/// Precomputed state for Rader's algorithm which maps a prime length `p` DFT
+/// onto a length `p - 1` cyclic convolution. The convolution is computed with 
+/// a length `p - 1` plan so any algorithm in the crate can be used for it
+#[derive(Debug, Clone)]
+pub(crate) struct Rader<F> {
+    inner: Box<Plan<F>>,
+    kernel_fft: Vec<Complex<F>>,
+    input_index: Vec<usize>,
+    output_index: Vec<usize>,
+    scratch: Vec<Complex<F>>,
+}
+
+impl<F> Rader<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{
+    pub(crate) fn new(p: usize) -> Self {
+        debug_assert!(is_prime(p));
+        let m = p - 1;
+        let g = primitive_root(p);
+        let g_inv = pow_mod(g, p - 2, p);
+
+        // The input is permuted by powers of the generator and the output by
+        // powers of its inverse
+        let mut input_index = Vec::with_capacity(m);
+        let mut output_index = Vec::with_capacity(m);
+        let (mut gq, mut gq_inv) = (1, 1);
+        for _ in 0..m {
+            input_index.push(gq);
+            output_index.push(gq_inv);
+            gq = gq * g % p;
+            gq_inv = gq_inv * g_inv % p;
+        }
+
+        let mut inner = Box::new(Plan::forward(m));
+        let theta = -F::TAU() / p.as_();
+        let mut kernel_fft: Vec<Complex<F>> = output_index.iter()
+            .map(|&k| Complex::from_polar(F::one(), theta * k.as_()))
+            .collect();
+        inner.process(&mut kernel_fft);
+        // Fold the normalization of the inverse convolution transform into 
+        // the kernel spectrum
+        let scale: F = m.as_();
+        kernel_fft.iter_mut().for_each(|k| *k /= scale);
+
+        let scratch = vec![Complex::new(F::zero(), F::zero()); m];
+        Self { inner, kernel_fft, input_index, output_index, scratch }
+    }
+
+    pub(crate) fn process(&mut self, x: &mut [Complex<F>]) {
+        let x0 = x[0];
+        self.scratch.iter_mut()
+            .zip(self.input_index.iter())
+            .for_each(|(s, &i)| *s = x[i]);
+        self.inner.process(&mut self.scratch);
+        // The first bin of the permuted input spectrum is the sum of all the
+        // items except `x[0]`
+        let sum = x0 + self.scratch[0];
+
+        // The inverse transform is computed as conj(fft(conj(x)))
+        self.scratch.iter_mut()
+            .zip(self.kernel_fft.iter())
+            .for_each(|(s, k)| *s = (*s * k).conj());
+        self.inner.process(&mut self.scratch);
+
+        self.output_index.iter()
+            .zip(self.scratch.iter())
+            .for_each(|(&i, s)| x[i] = x0 + s.conj());
+        x[0] = sum;
+    }
+}
+
+/// Computes the fast fourier transform of the prime length complex valued input
+/// collection with Rader's algorithm and outputs a complex valued collection.
+/// For composite lengths with a prime factor above 7 a `Plan` applies Rader's
+/// algorithm to that factor through the Good-Thomas algorithm
+pub fn fft<F, I>(x: &I) -> I
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    assert!(is_prime(n), "Rader's algorithm requires a prime length, got {}", n);
+    let mut y = x.clone();
+    Rader::new(n).process(y.as_mut_slice());
+    y
+}
+
+/// Computes the inverse fast fourier transform of the prime length complex 
+/// valued input collection with Rader's algorithm and outputs a complex valued 
+/// collection. 
+/// The output *is* normalized
+pub fn ifft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    // The inverse transform is computed as conj(fft(conj(x))) / n
+    let n_f: F = x.len().as_();
+    let conj: I = x.iter().map(|v| v.conj()).collect();
+    fft(&conj).iter().map(|v| v.conj() / n_f).collect()
+}
+
+/// Fallible version of `fft`, returns an error if the collection is empty or
+/// its length is not prime
+pub fn try_fft<F, I>(x: &I) -> Result<I, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(fft(x))
+}
+
+/// Fallible version of `ifft`, returns an error if the collection is empty or
+/// its length is not prime
+pub fn try_ifft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    check_len(x.len())?;
+    Ok(ifft(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ assert_nearly_equal, signal };
+
+    fn max_error(a: &[Complex<f64>], b: &[Complex<f64>]) -> f64 {
+        a.iter().zip(b.iter()).map(|(a, b)| (a - b).norm()).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        assert_eq!(primitive_root(2), 1);
+        assert_eq!(primitive_root(7), 3);
+        assert_eq!(primitive_root(23), 5);
+        assert_eq!(primitive_root(1009), 11);
+    }
+
+    #[test]
+    fn test_fft_rader_matches_dft() {
+        for p in [2, 3, 5, 11, 13, 17, 47, 257, 1009] {
+            let x = signal(p);
+            let output = fft(&x);
+            let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+            assert_nearly_equal(&output, &reference);
+        }
+    }
+
+    #[test]
+    fn test_ifft_rader_roundtrip() {
+        let x = signal(101);
+        let y: Vec<Complex<f64>> = ifft(&fft(&x));
+        assert_nearly_equal(&y, &x);
+    }
+
+    #[test]
+    fn test_fft_rader_accuracy_against_bluestein() {
+        let x = signal(1009);
+        let reference: Vec<Complex<f64>> = crate::fft::complex::dft(&x);
+        let rader = fft(&x);
+        let mut bluestein = x.clone();
+        crate::fft::czt::Bluestein::new(x.len()).process(&mut bluestein);
+        // Both algorithms should be well within the tolerance of the direct DFT
+        assert!(max_error(&rader, &reference) < 1e-9);
+        assert!(max_error(&bluestein, &reference) < 1e-9);
+    }
+}
@@ -0,0 +1,464 @@
+//! Real input fast fourier transforms which exploit the hermitian symmetry of
+//! the spectrum of a real valued signal, `X[n - k] = conj(X[k])`. Only the
+//! `n / 2 + 1` non-redundant bins are computed, in the same order as numpy's
+//! `rfft` and `irfft` functions.
+//!
+//! For even lengths the real input is packed into a half length complex signal,
+//! `z[k] = x[2k] + i x[2k + 1]`, transformed with a half length fft and then split
+//! into the spectra of the even and odd samples which are combined with a final
+//! radix-2 butterfly. Odd lengths fall back to a full length complex transform.
+//!
+//! `rfft_into` and `irfft_into` compute the same transforms into caller provided
+//! buffers without allocating and are available without the `std` feature.
+use num_complex::Complex;
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::{ fft_in_place_with_scratch, ifft_in_place_with_scratch, scratch_len };
+#[cfg(feature = "std")]
+use crate::fft::{ scale, plan::{ Direction, Plan }, Normalization };
+#[cfg(feature = "std")]
+use crate::traits::{ Iterable, IterableMut, Signal };
+
+/// Twiddle factor `exp(-2πik/n)` for the final butterfly of the real transform
+fn twiddle<F>(k: usize, n: usize) -> Complex<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    Complex::from_polar(F::one(), -F::TAU() * k.as_() / n.as_())
+}
+
+/// Splits the length `m` spectrum `z` of the packed signal `x[2k] + i x[2k + 1]`
+/// into the `m + 1` non-redundant bins of the length `2m` real spectrum
+pub(crate) fn split_spectrum<F>(z: &[Complex<F>], out: &mut [Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let m = z.len();
+    let n = 2 * m;
+    let half: F = F::one() / (F::one() + F::one());
+    for (k, bin) in out.iter_mut().enumerate().take(m + 1) {
+        let zk = z[k % m];
+        let zmk = z[(m - k) % m].conj();
+        let even = (zk + zmk) * half;
+        // Dividing by 2i
+        let odd = (zk - zmk) * half;
+        let odd = Complex::new(odd.im, -odd.re);
+        *bin = even + twiddle::<F>(k, n) * odd;
+    }
+}
+
+/// Inverse of `split_spectrum`, merges the `m + 1` non-redundant bins of a
+/// length `2m` real spectrum into the length `m` spectrum of the packed signal
+pub(crate) fn merge_spectrum<F>(x: &[Complex<F>], z: &mut [Complex<F>])
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let m = z.len();
+    let n = 2 * m;
+    let half: F = F::one() / (F::one() + F::one());
+    for (k, zk) in z.iter_mut().enumerate() {
+        let xk = x[k];
+        let xmk = x[m - k].conj();
+        let even = (xk + xmk) * half;
+        let odd = (xk - xmk) * half * twiddle::<F>(k, n).conj();
+        // Adding i times the odd spectrum
+        *zk = even + Complex::new(-odd.im, odd.re);
+    }
+}
+
+/// Computes the fast fourier transform of the real valued input collection and
+/// returns the `n / 2 + 1` non-redundant bins of the spectrum
+#[cfg(feature = "std")]
+pub fn rfft<F, I, C>(x: &I) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    rfft_norm(x, Normalization::Backward)
+}
+
+/// Computes the fast fourier transform of the real valued input collection 
+/// with the given normalization and returns the `n / 2 + 1` non-redundant bins
+/// of the spectrum
+#[cfg(feature = "std")]
+pub fn rfft_norm<F, I, C>(x: &I, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    let zero = Complex::new(F::zero(), F::zero());
+    let bins = n / 2 + 1;
+    if n.is_odd() || n == 0 {
+        let mut y: C = x.samples().map(|&v| Complex::new(v, F::zero())).collect();
+        Plan::with_normalization(n, Direction::Forward, norm).process(y.as_mut_slice());
+        return y.iter().take(bins).cloned().collect();
+    }
+
+    let m = n / 2;
+    let mut packed: C = x.samples()
+        .step_by(2)
+        .zip(x.samples().skip(1).step_by(2))
+        .map(|(&re, &im)| Complex::new(re, im))
+        .collect();
+    let packed = packed.as_mut_slice();
+    Plan::forward(m).process(packed);
+
+    let mut y = C::from_iter(core::iter::repeat_n(zero, bins));
+    split_spectrum(packed, y.as_mut_slice());
+    scale(y.as_mut_slice(), norm.forward_scale(n));
+    y
+}
+
+/// Computes the inverse fast fourier transform of the `n / 2 + 1` non-redundant
+/// bins of a real signal's spectrum and returns the length `n` real valued
+/// signal. Missing bins are treated as zero and extra bins are ignored. The
+/// imaginary parts of the zero frequency bin, and the nyquist bin for even `n`,
+/// are ignored.
+/// The output *is* normalized.
+#[cfg(feature = "std")]
+pub fn irfft<F, I, C>(x: &I, n: usize) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    irfft_norm(x, n, Normalization::Backward)
+}
+
+/// Computes the inverse fast fourier transform of the `n / 2 + 1` non-redundant
+/// bins of a real signal's spectrum with the given normalization and returns 
+/// the length `n` real valued signal. The bins are handled as in `irfft`
+#[cfg(feature = "std")]
+pub fn irfft_norm<F, I, C>(x: &I, n: usize, norm: Normalization) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    let zero = Complex::new(F::zero(), F::zero());
+    let bins = n / 2 + 1;
+    let mut half: I = x.iter()
+        .cloned()
+        .chain(core::iter::repeat(zero))
+        .take(bins)
+        .collect();
+    let half = half.as_mut_slice();
+    half[0].im = F::zero();
+    if n.is_even() {
+        half[bins - 1].im = F::zero();
+    }
+
+    if n.is_odd() {
+        let mut y: I = (0..n)
+            .map(|k| if k < bins { half[k] } else { half[n - k].conj() })
+            .collect();
+        Plan::with_normalization(n, Direction::Inverse, norm).process(y.as_mut_slice());
+        return y.iter().map(|v| v.re).collect();
+    }
+
+    let m = n / 2;
+    let mut packed: I = core::iter::repeat_n(zero, m).collect();
+    let packed = packed.as_mut_slice();
+    merge_spectrum(half, packed);
+    // The packed spectrum is scaled for a half length transform so the 
+    // unnormalized inverse is scaled by 2 on top of the requested normalization
+    Plan::with_normalization(m, Direction::Inverse, Normalization::Forward).process(packed);
+    let factor: F = norm.inverse_scale::<F>(n) * (F::one() + F::one());
+    packed.iter().flat_map(|z| [z.re * factor, z.im * factor]).collect()
+}
+
+/// Fallible version of `rfft`, returns an error if the collection is empty
+#[cfg(feature = "std")]
+pub fn try_rfft<F, I, C>(x: &I) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    C: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    if x.len() == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    Ok(rfft(x))
+}
+
+/// Fallible version of `irfft`, returns an error if the collection is empty or
+/// the output length `n` is zero
+#[cfg(feature = "std")]
+pub fn try_irfft<F, I, C>(x: &I, n: usize) -> Result<C, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> I: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    I: IterableMut,
+    usize: AsPrimitive<F>,
+{
+    if x.len() == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    if n == 0 {
+        return Err(RufftError::InvalidParameter("n"));
+    }
+    Ok(irfft(x, n))
+}
+
+/// The length of the scratch buffer required by `rfft_into` for a real valued 
+/// input of length `n`
+pub fn rfft_scratch_len(n: usize) -> usize {
+    if n.is_odd() {
+        n + scratch_len(n)
+    } else {
+        n / 2 + scratch_len(n / 2)
+    }
+}
+
+/// Computes the `n / 2 + 1` non-redundant bins of the spectrum of the real 
+/// valued slice into `out` without allocating, using the caller provided 
+/// scratch space which must hold at least `rfft_scratch_len(x.len())` items
+pub fn rfft_into<F>(x: &[F], out: &mut [Complex<F>], scratch: &mut [Complex<F>]) -> Result<(), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = x.len();
+    if n == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    let bins = n / 2 + 1;
+    if out.len() != bins {
+        return Err(RufftError::LengthMismatch { expected: bins, actual: out.len() });
+    }
+    let required = rfft_scratch_len(n);
+    if scratch.len() < required {
+        return Err(RufftError::LengthMismatch { expected: required, actual: scratch.len() });
+    }
+
+    if n.is_odd() {
+        let (buf, scratch) = scratch.split_at_mut(n);
+        buf.iter_mut()
+            .zip(x.iter())
+            .for_each(|(b, &v)| *b = Complex::new(v, F::zero()));
+        fft_in_place_with_scratch(buf, scratch)?;
+        out.copy_from_slice(&buf[..bins]);
+        return Ok(());
+    }
+
+    let (packed, scratch) = scratch.split_at_mut(n / 2);
+    packed.iter_mut()
+        .zip(x.chunks_exact(2))
+        .for_each(|(p, pair)| *p = Complex::new(pair[0], pair[1]));
+    fft_in_place_with_scratch(packed, scratch)?;
+    split_spectrum(packed, out);
+    Ok(())
+}
+
+/// The length of the scratch buffer required by `irfft_into` for a real valued
+/// output of length `n`
+pub fn irfft_scratch_len(n: usize) -> usize {
+    if n.is_odd() {
+        n + scratch_len(n)
+    } else {
+        n + 1 + scratch_len(n / 2)
+    }
+}
+
+/// Computes the length `out.len()` real valued inverse of the `n / 2 + 1` 
+/// non-redundant bins in `x` without allocating, using the caller provided 
+/// scratch space which must hold at least `irfft_scratch_len(out.len())` items.
+/// The imaginary parts of the zero frequency bin, and the nyquist bin for even
+/// lengths, are ignored.
+/// The output *is* normalized
+pub fn irfft_into<F>(x: &[Complex<F>], out: &mut [F], scratch: &mut [Complex<F>]) -> Result<(), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let n = out.len();
+    if n == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    let bins = n / 2 + 1;
+    if x.len() != bins {
+        return Err(RufftError::LengthMismatch { expected: bins, actual: x.len() });
+    }
+    let required = irfft_scratch_len(n);
+    if scratch.len() < required {
+        return Err(RufftError::LengthMismatch { expected: required, actual: scratch.len() });
+    }
+
+    if n.is_odd() {
+        let (buf, scratch) = scratch.split_at_mut(n);
+        buf.iter_mut()
+            .enumerate()
+            .for_each(|(k, b)| *b = if k < bins { x[k] } else { x[n - k].conj() });
+        buf[0].im = F::zero();
+        ifft_in_place_with_scratch(buf, scratch)?;
+        out.iter_mut().zip(buf.iter()).for_each(|(o, b)| *o = b.re);
+        return Ok(());
+    }
+
+    let (half, scratch) = scratch.split_at_mut(bins);
+    let (packed, scratch) = scratch.split_at_mut(n / 2);
+    half.copy_from_slice(x);
+    half[0].im = F::zero();
+    half[bins - 1].im = F::zero();
+    merge_spectrum(half, packed);
+    ifft_in_place_with_scratch(packed, scratch)?;
+    out.chunks_exact_mut(2)
+        .zip(packed.iter())
+        .for_each(|(pair, z)| {
+            pair[0] = z.re;
+            pair[1] = z.im;
+        });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ self as test, read_json, real_signal, Data, Json };
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    #[test]
+    fn test_rfft_dataset() {
+        let json_data: Json<f64> = read_json("datasets/fft/fft/fft.json");
+        let input: Array1<f64> = match json_data.input_data {
+            Data::Array(input) => input.into(),
+            _ => panic!("Read the input data incorrectly")
+        };
+        let output: Array1<Complex<f64>> = rfft(&input);
+        assert_eq!(output.len(), input.len() / 2 + 1);
+        match json_data.output_data {
+            Data::ComplexVals { mag, phase } => {
+                for i in 0..output.len() {
+                    let reference = Complex::from_polar(mag[i], phase[i]);
+                    assert!(test::nearly_equal_complex(output[i], reference, RTOL_F64, 1e-12),
+                        "{} => {} != {}", i, output[i], reference);
+                }
+            }
+            _ => panic!("Read the output data incorrectly")
+        }
+    }
+
+    #[test]
+    fn test_rfft_matches_dft() {
+        for n in [1, 2, 3, 8, 15, 100, 101, 1000] {
+            let x = real_signal(n);
+            let output: Vec<Complex<f64>> = rfft(&x);
+            let reference: Vec<Complex<f64>> = crate::fft::dft(&x);
+            test::assert_nearly_equal(&output, &reference[..n / 2 + 1]);
+        }
+    }
+
+    #[test]
+    fn test_irfft_roundtrip() {
+        for n in [1, 2, 3, 8, 15, 100, 101, 1000] {
+            let x = real_signal(n);
+            let spectrum: Vec<Complex<f64>> = rfft(&x);
+            let y: Vec<f64> = irfft(&spectrum, n);
+            assert_eq!(y.len(), n);
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64),
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rfft_irfft_norm() {
+        for n in [15, 16] {
+            let x = real_signal(n);
+            let reference: Vec<Complex<f64>> = rfft(&x);
+            for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+                let factor: f64 = norm.forward_scale(n);
+                let spectrum: Vec<Complex<f64>> = rfft_norm(&x, norm);
+                for (i, (&a, &b)) in spectrum.iter().zip(reference.iter()).enumerate() {
+                    assert!(test::nearly_equal_complex(a, b * factor, RTOL_F64, ATOL_F64),
+                        "n = {}, {} => {} != {}", n, i, a, b * factor);
+                }
+                let y: Vec<f64> = irfft_norm(&spectrum, n, norm);
+                for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                    assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64),
+                        "n = {}, {} => {} != {}", n, i, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_irfft_pads_and_truncates() {
+        let x = real_signal(16);
+        let spectrum: Vec<Complex<f64>> = rfft(&x);
+        // Truncating the spectrum removes the highest frequency bins
+        let truncated: Vec<Complex<f64>> = spectrum.iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, v)| if i < 5 { v } else { Complex::new(0.0, 0.0) })
+            .collect();
+        let a: Vec<f64> = irfft(&spectrum[..5].to_vec(), 16);
+        let b: Vec<f64> = irfft(&truncated, 16);
+        for (i, (&a, &b)) in a.iter().zip(b.iter()).enumerate() {
+            assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_rfft_into_irfft_into() {
+        for n in [1, 2, 3, 8, 15, 100, 101, 1000] {
+            let x = real_signal(n);
+            let reference: Vec<Complex<f64>> = rfft(&x);
+            let mut out = vec![Complex::new(0.0, 0.0); n / 2 + 1];
+            let mut scratch = vec![Complex::new(0.0, 0.0); rfft_scratch_len(n)];
+            rfft_into(&x, &mut out, &mut scratch).unwrap();
+            test::assert_nearly_equal(&out, &reference);
+            let mut y = vec![0.0; n];
+            let mut scratch = vec![Complex::new(0.0, 0.0); irfft_scratch_len(n)];
+            irfft_into(&out, &mut y, &mut scratch).unwrap();
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64),
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rfft_into_errors() {
+        let x = real_signal(8);
+        let mut out = vec![Complex::new(0.0, 0.0); 4];
+        let mut scratch = vec![Complex::new(0.0, 0.0); rfft_scratch_len(8)];
+        assert_eq!(rfft_into(&x, &mut out, &mut scratch), Err(RufftError::LengthMismatch { expected: 5, actual: 4 }));
+        let mut out = vec![Complex::new(0.0, 0.0); 5];
+        let mut scratch = vec![Complex::new(0.0, 0.0); 3];
+        assert_eq!(rfft_into(&x, &mut out, &mut scratch), Err(RufftError::LengthMismatch { expected: 4, actual: 3 }));
+        assert_eq!(rfft_into(&[], &mut out, &mut scratch), Err(RufftError::EmptyInput));
+    }
+
+    #[test]
+    fn test_try_rfft_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(try_rfft::<f64, _, Vec<Complex<f64>>>(&empty), Err(RufftError::EmptyInput));
+        let spectrum: Vec<Complex<f64>> = try_rfft(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(try_irfft::<f64, _, Vec<f64>>(&spectrum, 0), Err(RufftError::InvalidParameter("n")));
+        assert_eq!(try_irfft::<f64, _, Vec<f64>>(&spectrum[..0].to_vec(), 4), Err(RufftError::EmptyInput));
+        assert_eq!(try_irfft::<f64, _, Vec<f64>>(&spectrum, 4).unwrap().len(), 4);
+    }
+}
@@ -2,37 +2,39 @@
 //! `itertools` after the `itertool` crate
 
 pub mod complex;
+pub mod window;
 use num_traits::{ Float, FloatConst, NumAssign };
+use crate::error::RufftError;
 use crate::traits::Iterable;
 
 /// Clone and pad the real valued input collection with the floating 
 /// point type `F` to the length `len`
-pub fn pad<F, I>(x: &I, padding: F, len: usize) -> Result<I, ()> 
+/// Returns `RufftError::InvalidParameter` if `len` is shorter than the collection
+pub fn pad<F, I>(x: &I, padding: F, len: usize) -> Result<I, RufftError> 
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
 {
     let n = x.iter().len();
     if len < n {
-        return Err(());
+        return Err(RufftError::InvalidParameter("len"));
     }
-    let num_padding = len - n;
-    let pad_iter = core::iter::repeat(padding).take(num_padding);
+    let pad_iter = core::iter::repeat_n(padding, len - n);
     Ok(I::from_iter(x.iter().cloned().chain(pad_iter)))
 }
 
 /// Clone and zero pad the real valued input collection to the length `len`
-pub fn zero_pad<F, I>(n: usize, x: &I) -> Result<I, ()> 
+pub fn zero_pad<F, I>(n: usize, x: &I) -> Result<I, RufftError> 
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
 {
-    Ok(pad(x, F::zero(), n)?)
+    pad(x, F::zero(), n)
 }
 
 /// Clone and pad the real valued input collection with floating point value `F`
 /// to the nearest power of two length
-pub fn pad_to_nearest_power_of_two<F, I>(x: &I, padding: F) -> Result<I, ()>
+pub fn pad_to_nearest_power_of_two<F, I>(x: &I, padding: F) -> Result<I, RufftError>
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
@@ -42,13 +44,13 @@ where
     if n.is_power_of_two() {
         Ok(x.clone())
     } else {
-        Ok(pad(x, padding, n.next_power_of_two())?) // Pad to the nearest power of 2
+        pad(x, padding, n.next_power_of_two()) // Pad to the nearest power of 2
     }
 }
 
 /// Clone and zero pad the real valued input collection to the nearest power 
 /// of two length
-pub fn zero_pad_to_nearest_power_of_two<F, I>(x: &I) -> Result<I, ()>
+pub fn zero_pad_to_nearest_power_of_two<F, I>(x: &I) -> Result<I, RufftError>
 where
     F: Float + FloatConst + NumAssign + 'static,
     for<'c> I: Iterable<OwnedItem = F, Item<'c> = &'c F>,
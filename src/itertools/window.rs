@@ -0,0 +1,364 @@
+//! Window functions matching the definitions of `scipy.signal.windows`. Each
+//! window is computed in a symmetric form, for filter design, or a periodic
+//! form, for spectral analysis, which is the symmetric window of length
+//! `n + 1` with the last sample removed. This is equivalent to the `sym`
+//! argument of the scipy windows.
+//!
+//! ```
+//! use rufft::itertools::window::{ self, Symmetry, Window };
+//!
+//! let w: Vec<f64> = window::get_window(Window::Hann, 8, Symmetry::Periodic);
+//! let x = vec![1.0; 8];
+//! let y: Vec<f64> = window::apply_window(&x, Window::Hann, Symmetry::Periodic);
+//! assert_eq!(w, y);
+//! ```
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::traits::{ Iterable, Signal };
+
+/// The form of a window, equivalent to the `sym` argument of scipy's windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// A window which is symmetric about its center, used for filter design
+    Symmetric,
+    /// A window with a period of its length, used for spectral analysis
+    Periodic,
+}
+
+/// Window functions and their parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window<F> {
+    /// The rectangular window of ones
+    Boxcar,
+    Hann,
+    Hamming,
+    Blackman,
+    /// The minimum 4-term Blackman-Harris window
+    BlackmanHarris,
+    /// Nuttall's minimum 4-term Blackman-Harris window
+    Nuttall,
+    FlatTop,
+    /// The triangular window with zeros at both ends
+    Bartlett,
+    /// The tapered cosine window, `alpha` is the fraction of the window inside
+    /// the cosine tapered region
+    Tukey { alpha: F },
+    /// The Kaiser window, `beta` sets the trade off between the main lobe width
+    /// and the side lobe level
+    Kaiser { beta: F },
+    /// The gaussian window with the standard deviation `std` in samples
+    Gaussian { std: F },
+    /// The Dolph-Chebyshev window with a side lobe `attenuation` in decibels.
+    /// Computing the window takes `O(n²)` time
+    Chebyshev { attenuation: F },
+}
+
+/// Computes the generalized cosine window `Σ a[k] cos(k θ)` for `θ` linearly
+/// spaced from `-π` to `π`
+fn general_cosine<F>(coefficients: &[f64], i: usize, m: usize) -> F
+where
+    F: Float + FloatConst + 'static,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    let theta = -F::PI() + F::TAU() * i.as_() / (m - 1).as_();
+    coefficients.iter()
+        .enumerate()
+        .fold(F::zero(), |acc, (k, &a)| acc + a.as_() * (theta * k.as_()).cos())
+}
+
+/// Modified Bessel function of the first kind of order zero, computed from its
+/// power series `Σ ((x / 2)^k / k!)²`
+fn bessel_i0<F: Float + 'static>(x: F) -> F
+where
+    usize: AsPrimitive<F>,
+{
+    let half = x / (F::one() + F::one());
+    let mut term = F::one();
+    let mut sum = F::one();
+    let mut k = 1;
+    while term > sum * F::epsilon() {
+        let factor = half / k.as_();
+        term = term * factor * factor;
+        sum = sum + term;
+        k += 1;
+    }
+    sum
+}
+
+/// Chebyshev polynomial `T_order(x)` of the Dolph-Chebyshev window, where the
+/// sign of the polynomial for `x < -1` depends on the parity of the length
+fn chebyshev_poly<F: Float>(order: F, x: F, m: usize) -> F {
+    if x > F::one() {
+        (order * x.acosh()).cosh()
+    } else if x < -F::one() {
+        let sign = if m % 2 == 1 { F::one() } else { -F::one() };
+        sign * (order * (-x).acosh()).cosh()
+    } else {
+        (order * x.acos()).cos()
+    }
+}
+
+/// Precomputed state of the Dolph-Chebyshev window, the window is the real
+/// part of the DFT of the Chebyshev polynomial sampled around the unit circle
+#[derive(Debug, Clone, Copy)]
+struct Chebyshev<F> {
+    m: usize,
+    beta: F,
+    max: F,
+}
+
+impl<F> Chebyshev<F>
+where
+    F: Float + FloatConst + 'static,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    fn new(m: usize, attenuation: F) -> Self {
+        let order: F = (m - 1).as_();
+        let ten: F = 10.0.as_();
+        let twenty: F = 20.0.as_();
+        let beta = ((ten.powf(attenuation.abs() / twenty)).acosh() / order).cosh();
+        let unnormalized = Self { m, beta, max: F::one() };
+        let max = (0..m)
+            .map(|i| unnormalized.sample(i))
+            .fold(F::neg_infinity(), F::max);
+        Self { max, ..unnormalized }
+    }
+
+    /// The `j`th bin of the real part of the DFT
+    fn bin(&self, j: usize) -> F {
+        let m = self.m;
+        let order: F = (m - 1).as_();
+        let m_f: F = m.as_();
+        (0..m).fold(F::zero(), |acc, k| {
+            let k_f: F = k.as_();
+            let p = chebyshev_poly(order, self.beta * (F::PI() * k_f / m_f).cos(), m);
+            // Even lengths are shifted by half a sample
+            let theta = if m % 2 == 1 {
+                F::TAU() * ((j * k) % m).as_() / m_f
+            } else {
+                F::PI() * k_f * (F::one() - (2 * j).as_()) / m_f
+            };
+            acc + p * theta.cos()
+        })
+    }
+
+    fn sample(&self, i: usize) -> F {
+        let m = self.m;
+        let bin = if m % 2 == 1 {
+            let center = (m - 1) / 2;
+            self.bin(i.abs_diff(center))
+        } else if i < m / 2 {
+            self.bin(m / 2 - i)
+        } else {
+            self.bin(i + 1 - m / 2)
+        };
+        bin / self.max
+    }
+}
+
+/// Computes the sample `i` of the symmetric window of length `m`
+fn sample<F>(window: &Window<F>, chebyshev: Option<&Chebyshev<F>>, i: usize, m: usize) -> F
+where
+    F: Float + FloatConst + 'static,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    if m == 1 {
+        return F::one();
+    }
+    let two = F::one() + F::one();
+    let i_f: F = i.as_();
+    let last: F = (m - 1).as_();
+    match *window {
+        Window::Boxcar => F::one(),
+        Window::Hann => general_cosine(&[0.5, 0.5], i, m),
+        Window::Hamming => general_cosine(&[0.54, 0.46], i, m),
+        Window::Blackman => general_cosine(&[0.42, 0.50, 0.08], i, m),
+        Window::BlackmanHarris => general_cosine(&[0.35875, 0.48829, 0.14128, 0.01168], i, m),
+        Window::Nuttall => general_cosine(&[0.3635819, 0.4891775, 0.1365995, 0.0106411], i, m),
+        Window::FlatTop => general_cosine(
+            &[0.21557895, 0.41663158, 0.277263158, 0.083578947, 0.006947368], i, m
+        ),
+        Window::Bartlett => {
+            if i_f <= last / two {
+                two * i_f / last
+            } else {
+                two - two * i_f / last
+            }
+        },
+        Window::Tukey { alpha } => {
+            if alpha <= F::zero() {
+                F::one()
+            } else if alpha >= F::one() {
+                general_cosine(&[0.5, 0.5], i, m)
+            } else {
+                let width = (alpha * last / two).floor().to_usize().unwrap_or(0);
+                if i <= width {
+                    (F::one() + (F::PI() * (-F::one() + two * i_f / alpha / last)).cos()) / two
+                } else if i < m - width - 1 {
+                    F::one()
+                } else {
+                    let phase = -two / alpha + F::one() + two * i_f / alpha / last;
+                    (F::one() + (F::PI() * phase).cos()) / two
+                }
+            }
+        },
+        Window::Kaiser { beta } => {
+            let center = last / two;
+            let ratio = (i_f - center) / center;
+            bessel_i0(beta * (F::one() - ratio * ratio).max(F::zero()).sqrt()) / bessel_i0(beta)
+        },
+        Window::Gaussian { std } => {
+            let offset = i_f - last / two;
+            (-offset * offset / (two * std * std)).exp()
+        },
+        Window::Chebyshev { .. } => chebyshev
+            .expect("The Chebyshev window state is precomputed")
+            .sample(i),
+    }
+}
+
+/// Returns an iterator over the `n` samples of the window
+pub fn iter<F>(window: Window<F>, n: usize, symmetry: Symmetry) -> impl ExactSizeIterator<Item = F>
+where
+    F: Float + FloatConst + 'static,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    // Periodic windows are the symmetric window of length n + 1 truncated to n,
+    // windows of a single sample are always one
+    let m = match symmetry {
+        Symmetry::Periodic if n > 1 => n + 1,
+        _ => n,
+    };
+    let chebyshev = match window {
+        Window::Chebyshev { attenuation } if n > 0 && m > 1 => Some(Chebyshev::new(m, attenuation)),
+        _ => None,
+    };
+    (0..n).map(move |i| sample(&window, chebyshev.as_ref(), i, m))
+}
+
+/// Computes the `n` samples of the window in the given form and collects them
+/// into the output collection
+pub fn get_window<F, C>(window: Window<F>, n: usize, symmetry: Symmetry) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    iter(window, n, symmetry).collect()
+}
+
+/// Multiplies the real valued input collection by the window of the same
+/// length in the given form
+pub fn apply_window<F, I, C>(x: &I, window: Window<F>, symmetry: Symmetry) -> C
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    x.samples()
+        .zip(iter(window, x.len(), symmetry))
+        .map(|(&v, w)| v * w)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ self as test, read_json, Data, Json };
+    use ndarray::prelude::*;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    /// Compares the window against the scipy dataset, `window` creates the
+    /// window from the parameter stored in the dataset
+    fn test_window<C>(path: &str, window: impl Fn(f64) -> Window<f64>, symmetry: Symmetry)
+    where
+        for<'c> C: Iterable<OwnedItem = f64, Item<'c> = &'c f64>,
+    {
+        let json_data: Json<f64> = read_json(path);
+        let (n, param) = match json_data.input_data {
+            Data::WindowVals { n, param } => (n as usize, param),
+            _ => panic!("Read the input data incorrectly")
+        };
+        let reference = match json_data.output_data {
+            Data::Array(output) => output,
+            _ => panic!("Read the output data incorrectly")
+        };
+        let output: C = get_window(window(param), n, symmetry);
+        assert_eq!(output.len(), reference.len());
+        for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+            assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} {} => {} != {}", path, i, a, b);
+        }
+    }
+
+    fn test_both<C>(name: &str, window: impl Fn(f64) -> Window<f64>)
+    where
+        for<'c> C: Iterable<OwnedItem = f64, Item<'c> = &'c f64>,
+    {
+        test_window::<C>(&format!("datasets/wavegen/{0}/{0}.json", name), &window, Symmetry::Symmetric);
+        test_window::<C>(&format!("datasets/wavegen/{0}_periodic/{0}_periodic.json", name), &window, Symmetry::Periodic);
+    }
+
+    #[test]
+    fn test_cosine_windows_vec_f64() {
+        test_both::<Vec<f64>>("boxcar", |_| Window::Boxcar);
+        test_both::<Vec<f64>>("hann", |_| Window::Hann);
+        test_both::<Vec<f64>>("hamming", |_| Window::Hamming);
+        test_both::<Vec<f64>>("blackman", |_| Window::Blackman);
+        test_both::<Vec<f64>>("blackmanharris", |_| Window::BlackmanHarris);
+        test_both::<Vec<f64>>("nuttall", |_| Window::Nuttall);
+        test_both::<Vec<f64>>("flattop", |_| Window::FlatTop);
+    }
+
+    #[test]
+    fn test_parametric_windows_arr_f64() {
+        test_both::<Array1<f64>>("bartlett", |_| Window::Bartlett);
+        test_both::<Array1<f64>>("tukey", |alpha| Window::Tukey { alpha });
+        test_both::<Array1<f64>>("kaiser", |beta| Window::Kaiser { beta });
+        test_both::<Array1<f64>>("gaussian", |std| Window::Gaussian { std });
+        test_both::<Array1<f64>>("chebwin", |attenuation| Window::Chebyshev { attenuation });
+    }
+
+    #[test]
+    fn test_short_windows() {
+        let windows = [
+            Window::Boxcar, Window::Hann, Window::Bartlett, Window::Tukey { alpha: 0.5 },
+            Window::Kaiser { beta: 8.6 }, Window::Gaussian { std: 1.0 },
+            Window::Chebyshev { attenuation: 100.0 },
+        ];
+        for window in windows {
+            for symmetry in [Symmetry::Symmetric, Symmetry::Periodic] {
+                let w: Vec<f64> = get_window(window, 0, symmetry);
+                assert!(w.is_empty());
+                let w: Vec<f64> = get_window(window, 1, symmetry);
+                assert_eq!(w, vec![1.0], "{:?} {:?}", window, symmetry);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tukey_limits() {
+        let hann: Vec<f64> = get_window(Window::Hann, 16, Symmetry::Symmetric);
+        let tukey: Vec<f64> = get_window(Window::Tukey { alpha: 1.0 }, 16, Symmetry::Symmetric);
+        assert_eq!(hann, tukey);
+        let tukey: Vec<f64> = get_window(Window::Tukey { alpha: 0.0 }, 16, Symmetry::Symmetric);
+        assert!(tukey.iter().all(|&v| v == 1.0));
+    }
+
+    #[test]
+    fn test_apply_window() {
+        let x: Vec<f64> = (0..32).map(|i| (i as f64 * 0.3).sin()).collect();
+        let w: Vec<f64> = get_window(Window::Blackman, 32, Symmetry::Periodic);
+        let y: Vec<f64> = apply_window(&x[..], Window::Blackman, Symmetry::Periodic);
+        for ((&a, &b), &v) in y.iter().zip(w.iter()).zip(x.iter()) {
+            assert_eq!(a, b * v);
+        }
+    }
+}
@@ -21,12 +21,48 @@
 //! let _: Vec<Complex<f64>> = arr.fft();
 //! ```
 //!
+//! The input of a transform only needs to implement the `rufft::traits::Signal` trait,
+//! which is implemented for slices, fixed size arrays, `Box<[T]>`, `VecDeque` and
+//! one dimensional `ndarray` arrays and views, so borrowed data is not copied first.
+//!
+//! ```
+//! use rufft::{Complex, traits::Fft};
+//!
+//! let arr = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! let _: Vec<Complex<f64>> = arr[1..].fft();
+//! ```
+//!
+//! The `rufft::traits::Ifft` trait is the inverse of `Fft`, it is blanket implemented
+//! on `Signal` types of `Complex<F>` values, including slices and array views, and
+//! returns a collection of the real valued inverse transform.
+//!
+//! ```
+//! // Perform an fft and ifft on a Vec of floats
+//! use rufft::{Complex, traits::{Fft, Ifft}};
+//!
+//! let arr = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+//! let spectrum: Vec<Complex<f64>> = arr.fft();
+//! let _: Vec<f64> = spectrum.ifft();
+//! ```
+//!
+//! Collections of complex values are transformed with the `rufft::traits::ComplexFft`
+//! and `rufft::traits::ComplexIfft` traits which keep the complex valued output of
+//! the inverse transform.
+//!
+//! ```
+//! use rufft::{Complex, traits::{ComplexFft, ComplexIfft}};
+//!
+//! let arr = vec![Complex::new(1.0, 0.5), Complex::new(2.0, -1.0), Complex::new(3.0, 0.0)];
+//! let spectrum: Vec<Complex<f64>> = arr.complex_fft();
+//! let _: Vec<Complex<f64>> = spectrum.complex_ifft();
+//! ```
+//!
 //! Rufft also exposes individual FFT algorithms in the `fft` module. Currently at the time
-//! of writing only the basic discrete fourier transfrom, `dft`, the cooley-tukey fft 
-//! algorithm `fft::ct::fft` and the chirp-z fft `fft::czt::fft`. The inverse transform is 
-//! currently unsupported for the Chirp-Z transform. I am still learning about fast fourier
-//! transform algorithms and will add more as time goes on. Any contributions there would be
-//! appreciated.
+//! of writing the basic discrete fourier transfrom, `dft`, the cooley-tukey fft 
+//! algorithm `fft::ct::fft`, the mixed-radix fft `fft::mixed::fft` and the chirp-z fft 
+//! `fft::czt::fft` along with their inverse transforms. I am still learning about fast
+//! fourier transform algorithms and will add more as time goes on. Any contributions 
+//! there would be appreciated.
 //! 
 //!
 //! ```
@@ -42,21 +78,58 @@
 //! * `std` (Enabled by default)
 //!
 //!     Links with rust's std crate, enables `std` feature in dependecies and 
-//!     provides a `Iterable` implementation for Vec. Vec technically is from the 
-//!     `alloc` crate and re-exported in `std` but this will do for now
+//!     `alloc`. Plans, the real, multidimensional and trigonometric transforms
+//!     and the prime length algorithms require `std`
+//!
+//! * `alloc`
+//!
+//!     Links with rust's alloc crate and provides `Iterable` and `Signal` 
+//!     implementations for `Vec`, `Box<[T]>` and `VecDeque` without `std`.
+//!     The `Fft`, `Ifft`, `ComplexFft` and `ComplexIfft` traits and the 
+//!     mixed-radix transforms returning collections require `alloc`
 //!
 //!     
 //! * `ndarray`
 //!
 //!     Re-exports the ndarray scientific computing crate and provides an 
-//!     `Iterable` trait implementation for the `ndarray::Array1` type
+//!     `Iterable` trait implementation for the `ndarray::Array1` type. Together
+//!     with `std` it enables the `spectral` module of short time fourier 
+//!     transforms and spectral estimates
+//!
+//! * `rayon`
+//!
+//!     Enables `std` and computes the halves of long radix-2 transforms, the
+//!     transforms of Bluestein's algorithm, batched plan transforms and the 
+//!     lanes of multidimensional transforms on the rayon thread pool. Only 
+//!     `f32` and `f64` transforms are parallelized
+//!
+//! Without any features the crate is `no_std` and allocation free. The functions
+//! `fft::fft_in_place_with_scratch` and `fft::real::rfft_into` transform caller 
+//! provided buffers, and the `Signal` trait is implemented for slices and arrays.
+//!
+//! ```
+//! use rufft::{Complex, fft};
+//!
+//! let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! let mut out = [Complex::new(0.0, 0.0); 4];
+//! let mut scratch = [Complex::new(0.0, 0.0); 9];
+//! assert!(fft::real::rfft_scratch_len(x.len()) <= scratch.len());
+//! fft::real::rfft_into(&x, &mut out, &mut scratch).unwrap();
+//! ```
 //!
 
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod fft;
 pub mod traits;
 pub mod itertools;
+pub mod error;
+#[cfg(all(feature = "std", feature = "ndarray"))]
+pub mod spectral;
+
+pub use error::RufftError;
 
 pub use num_complex::Complex;
 pub use num_traits::{ Float, FloatConst };
@@ -0,0 +1,351 @@
+//! Spectral analysis of real valued signals built on the real and complex fast
+//! fourier transforms, returning `ndarray` arrays. The signal is split into
+//! overlapping segments of `nperseg` samples which start every
+//! `nperseg - noverlap` samples, each segment is multiplied by a window and
+//! zero padded to `nfft` samples before it is transformed. The parameters and
+//! outputs follow the functions of the same names in `scipy.signal`.
+//!
+//! ```
+//! use rufft::{ spectral, itertools::window::Window };
+//!
+//! let x: Vec<f64> = (0..256).map(|i| (0.2 * i as f64).sin()).collect();
+//! let (freqs, times, zxx) = spectral::stft(&x, 100.0, Window::Hann, 64, 48, 64).unwrap();
+//! assert_eq!(zxx.dim(), (times.len(), freqs.len()));
+//! let (_, y) = spectral::istft(&zxx, 100.0, Window::Hann, 64, 48, 64).unwrap();
+//! assert!((y[100] - x[100]).abs() < 1e-10);
+//! ```
+//!
+//! The spectral estimates take the segmentation as a `Segments`, which also
+//! sets how each segment is detrended.
+//!
+//! ```
+//! use rufft::{ spectral::{ self, Average, Mode, Scaling, Segments, Sides }, itertools::window::Window };
+//!
+//! let x: Vec<f64> = (0..256).map(|i| (0.2 * i as f64).sin()).collect();
+//! let segments = Segments::new(Window::Hann, 64);
+//! let (freqs, times, sxx) = spectral::spectrogram(
+//!     &x, 100.0, &segments, Sides::OneSided, Scaling::Density, Mode::Psd
+//! ).unwrap();
+//! assert_eq!(sxx.dim(), (times.len(), freqs.len()));
+//!
+//! let (freqs, pxx) = spectral::welch(
+//!     &x, 100.0, &segments, Sides::OneSided, Scaling::Density, Average::Median
+//! ).unwrap();
+//! assert_eq!(pxx.len(), freqs.len());
+//! ```
+//!
+//! The cross spectral estimates relate two signals sampled together, such as
+//! the input and output of a system.
+//!
+//! ```
+//! use rufft::{ spectral::{ self, Estimator, Segments, Sides }, itertools::window::Window };
+//!
+//! let x: Vec<f64> = (0..256).map(|i| (0.2 * i as f64).sin() + (0.7 * i as f64).cos()).collect();
+//! let y: Vec<f64> = x.iter().map(|v| 3.0 * v).collect();
+//! let segments = Segments::new(Window::Hann, 64);
+//! let (_, cxy) = spectral::coherence(&x, &y, 100.0, &segments).unwrap();
+//! assert!(cxy.iter().all(|c| (c - 1.0).abs() < 1e-9));
+//! let (_, h) = spectral::transfer_function(&x, &y, 100.0, &segments, Sides::OneSided, Estimator::H1).unwrap();
+//! assert!(h.iter().all(|v| (v.re - 3.0).abs() < 1e-9));
+//! ```
+use ndarray::{ Array1, Array2, ArrayViewMut1 };
+use num_complex::Complex;
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::{ fftfreq, fftfreq_balanced };
+use crate::itertools::window::Window;
+use crate::traits::Fft;
+
+mod stft;
+mod spectrogram;
+mod welch;
+mod csd;
+
+pub use stft::{ stft, istft, check_cola, check_nola };
+pub use spectrogram::{ spectrogram, Mode };
+pub use welch::{ welch, periodogram, Average };
+pub use csd::{ csd, coherence, transfer_function, Estimator };
+
+/// The frequencies of the bins, the times of the segment centres and the
+/// `segments x bins` array of a time-frequency analysis
+pub type TimeFrequency<F, T> = (Array1<F>, Array1<F>, Array2<T>);
+
+/// The trend removed from each segment before it is windowed, equivalent to
+/// the `detrend` argument of the scipy functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Detrend {
+    /// The segment is not detrended
+    None,
+    /// The mean of the segment is removed
+    Constant,
+    /// The least squares line through the segment is removed
+    Linear,
+}
+
+impl Detrend {
+    /// Removes the trend from the segment in-place
+    pub fn apply<F>(self, x: &mut [F])
+    where
+        F: Float + NumAssign + 'static,
+        usize: AsPrimitive<F>,
+    {
+        if x.is_empty() {
+            return;
+        }
+        let n: F = x.len().as_();
+        let mean = x.iter().fold(F::zero(), |acc, &v| acc + v) / n;
+        match self {
+            Self::None => {},
+            Self::Constant => x.iter_mut().for_each(|v| *v -= mean),
+            Self::Linear => {
+                let centre = (n - F::one()) / (F::one() + F::one());
+                let (cov, var) = x.iter()
+                    .enumerate()
+                    .map(|(i, &v)| {
+                        let t = i.as_() - centre;
+                        (t * (v - mean), t * t)
+                    })
+                    .fold((F::zero(), F::zero()), |(c, s), (a, b)| (c + a, s + b));
+                let slope = if var > F::zero() { cov / var } else { F::zero() };
+                x.iter_mut()
+                    .enumerate()
+                    .for_each(|(i, v)| *v -= mean + slope * (i.as_() - centre));
+            },
+        }
+    }
+}
+
+/// The normalization of a power spectrum, equivalent to the `scaling` argument
+/// of the scipy functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scaling {
+    /// The power spectral density in units of `V²/Hz` for a signal in `V`
+    Density,
+    /// The power spectrum in units of `V²`, the squared amplitude of a sinusoid
+    /// at the frequency of a bin
+    Spectrum,
+}
+
+impl Scaling {
+    /// The factor a squared magnitude spectrum of a segment multiplied by the
+    /// window is scaled by
+    pub(crate) fn factor<F: Float>(self, win: &[F], fs: F) -> F {
+        match self {
+            Self::Density => {
+                let energy = win.iter().fold(F::zero(), |acc, &w| acc + w * w);
+                F::one() / (fs * energy)
+            },
+            Self::Spectrum => {
+                let sum = win.iter().fold(F::zero(), |acc, &w| acc + w);
+                F::one() / (sum * sum)
+            },
+        }
+    }
+}
+
+/// The frequency bins of a spectrum, equivalent to the `return_onesided`
+/// argument of the scipy functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sides {
+    /// The `nfft / 2 + 1` non-negative frequency bins of a real signal's
+    /// spectrum, the power of the negative frequencies is added to the
+    /// positive frequencies
+    OneSided,
+    /// All `nfft` bins in the order of `fftfreq_balanced`
+    TwoSided,
+}
+
+impl Sides {
+    /// The number of bins of a length `nfft` spectrum
+    pub(crate) fn bins(self, nfft: usize) -> usize {
+        match self {
+            Self::OneSided => nfft / 2 + 1,
+            Self::TwoSided => nfft,
+        }
+    }
+}
+
+/// The segmentation of a signal for a spectral estimate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segments<F> {
+    /// The window each segment is multiplied by, used in its periodic form
+    pub window: Window<F>,
+    /// The number of samples in each segment
+    pub nperseg: usize,
+    /// The number of samples consecutive segments overlap by, less than `nperseg`
+    pub noverlap: usize,
+    /// The length each segment is zero padded to before it is transformed, at
+    /// least `nperseg`
+    pub nfft: usize,
+    /// The trend removed from each segment before it is windowed
+    pub detrend: Detrend,
+}
+
+impl<F> Segments<F> {
+    /// Segments of `nperseg` samples overlapping by half a segment which are
+    /// not zero padded and have their mean removed. Note that scipy's
+    /// `spectrogram` defaults to an overlap of `nperseg / 8` instead
+    pub fn new(window: Window<F>, nperseg: usize) -> Self {
+        Self { window, nperseg, noverlap: nperseg / 2, nfft: nperseg, detrend: Detrend::Constant }
+    }
+
+    /// The number of samples between the starts of consecutive segments
+    pub fn step(&self) -> usize {
+        self.nperseg - self.noverlap
+    }
+
+    /// The number of complete segments of a signal of `n` samples
+    pub fn count(&self, n: usize) -> usize {
+        if n < self.nperseg {
+            0
+        } else {
+            (n - self.noverlap) / self.step()
+        }
+    }
+
+    /// Checks the segmentation of a signal of `n` samples
+    pub(crate) fn check(&self, n: usize) -> Result<(), RufftError> {
+        if n == 0 {
+            return Err(RufftError::EmptyInput);
+        }
+        check_segments(self.nperseg, self.noverlap, self.nfft)?;
+        if self.nperseg > n {
+            return Err(RufftError::InvalidParameter("nperseg"));
+        }
+        Ok(())
+    }
+}
+
+/// Checks the segment length, overlap and transform length shared by the
+/// spectral estimates
+pub(crate) fn check_segments(nperseg: usize, noverlap: usize, nfft: usize) -> Result<(), RufftError> {
+    if nperseg == 0 {
+        return Err(RufftError::InvalidParameter("nperseg"));
+    }
+    if noverlap >= nperseg {
+        return Err(RufftError::InvalidParameter("noverlap"));
+    }
+    if nfft < nperseg {
+        return Err(RufftError::InvalidParameter("nfft"));
+    }
+    Ok(())
+}
+
+/// Checks that the sampling frequency is positive and finite
+pub(crate) fn check_fs<F: Float>(fs: F) -> Result<(), RufftError> {
+    if fs > F::zero() && fs.is_finite() {
+        Ok(())
+    } else {
+        Err(RufftError::InvalidParameter("fs"))
+    }
+}
+
+/// The frequencies of the `nfft / 2 + 1` non-redundant bins of a real
+/// transform of length `nfft` at the sampling frequency `fs`
+pub(crate) fn rfftfreq<F>(nfft: usize, fs: F) -> Array1<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    fftfreq::<F, Vec<F>>(nfft, F::one() / fs)
+        .into_iter()
+        .take(nfft / 2 + 1)
+        .collect()
+}
+
+/// The frequencies of the bins of a length `nfft` spectrum at the sampling
+/// frequency `fs`
+pub(crate) fn frequencies<F>(nfft: usize, fs: F, sides: Sides) -> Array1<F>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+{
+    match sides {
+        Sides::OneSided => rfftfreq(nfft, fs),
+        Sides::TwoSided => fftfreq_balanced(nfft, F::one() / fs),
+    }
+}
+
+/// Detrends each segment of the signal, multiplies it by the window and zero
+/// pads it to `nfft` samples before transforming it. Returns the
+/// `segments x bins` array of the unscaled spectra of the segments
+pub(crate) fn segment_spectra<F>(x: &[F], segments: &Segments<F>, win: &[F], sides: Sides) -> Array2<Complex<F>>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let nperseg = segments.nperseg;
+    let mut spectra = Array2::zeros((segments.count(x.len()), sides.bins(segments.nfft)));
+    let mut buf = vec![F::zero(); segments.nfft];
+    for (mut row, segment) in spectra.rows_mut().into_iter().zip(x.windows(nperseg).step_by(segments.step())) {
+        buf[..nperseg].copy_from_slice(segment);
+        segments.detrend.apply(&mut buf[..nperseg]);
+        buf.iter_mut().zip(win.iter()).for_each(|(b, &w)| *b *= w);
+        let spectrum: Vec<Complex<F>> = buf.fft();
+        row.iter_mut().zip(spectrum).for_each(|(r, s)| *r = s);
+    }
+    spectra
+}
+
+/// Doubles the bins of a one-sided power spectrum which also represent the
+/// negative frequencies, all but the zero frequency and, for even `nfft`, the
+/// nyquist frequency bins
+pub(crate) fn double_one_sided<T>(mut x: ArrayViewMut1<T>, nfft: usize)
+where
+    T: Copy + core::ops::Add<Output = T>,
+{
+    let end = if nfft.is_even() { x.len() - 1 } else { x.len() };
+    x.iter_mut()
+        .take(end)
+        .skip(1)
+        .for_each(|v| *v = *v + *v);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils as test;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    #[test]
+    fn test_detrend() {
+        let x: Vec<f64> = (0..9).map(|i| 2.0 * i as f64 - 3.0).collect();
+        let mut y = x.clone();
+        Detrend::Linear.apply(&mut y);
+        assert!(y.iter().all(|v| v.abs() < ATOL_F64));
+        let mut y = x.clone();
+        Detrend::Constant.apply(&mut y);
+        for (i, &v) in y.iter().enumerate() {
+            assert!(test::nearly_equal(v, 2.0 * i as f64 - 8.0, RTOL_F64, ATOL_F64));
+        }
+        let mut y = x.clone();
+        Detrend::None.apply(&mut y);
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn test_segments() {
+        let segments = Segments::new(Window::<f64>::Hann, 16);
+        assert_eq!((segments.noverlap, segments.nfft, segments.step()), (8, 16, 8));
+        assert_eq!(segments.count(15), 0);
+        assert_eq!(segments.count(16), 1);
+        assert_eq!(segments.count(31), 2);
+        assert_eq!(segments.count(32), 3);
+        assert_eq!(segments.check(0), Err(RufftError::EmptyInput));
+        assert_eq!(segments.check(15), Err(RufftError::InvalidParameter("nperseg")));
+        let segments = Segments { nfft: 8, ..segments };
+        assert_eq!(segments.check(32), Err(RufftError::InvalidParameter("nfft")));
+    }
+
+    #[test]
+    fn test_frequencies() {
+        let one: Array1<f64> = frequencies(8, 16.0, Sides::OneSided);
+        assert_eq!(one.to_vec(), vec![0.0, 2.0, 4.0, 6.0, 8.0]);
+        let two: Array1<f64> = frequencies(5, 10.0, Sides::TwoSided);
+        assert_eq!(two.to_vec(), vec![0.0, 2.0, 4.0, -4.0, -2.0]);
+    }
+}
@@ -0,0 +1,332 @@
+//! Cross spectral analysis of two signals sampled together, by Welch's method
+//! applied to the cross spectra `conj(X) Y` of the segments. The cross power
+//! spectral density gives the magnitude-squared coherence of the signals and
+//! estimates of the transfer function of a system with input `x` and output `y`.
+use ndarray::{ Array1, Array2, Zip };
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::itertools::window::{ get_window, Symmetry };
+use crate::spectral::{
+    check_fs, double_one_sided, frequencies, segment_spectra, Average, Scaling, Segments, Sides
+};
+use crate::traits::Signal;
+
+/// The estimator of the transfer function from the input `x` to the output `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Estimator {
+    /// `Pxy / Pxx`, unbiased by noise on the output
+    H1,
+    /// `Pyy / Pyx`, unbiased by noise on the input
+    H2,
+}
+
+/// The averaged and scaled cross spectrum `conj(X) Y` of the spectra of the
+/// segments of two signals
+fn cross_spectrum<F>(
+    sx: &Array2<Complex<F>>,
+    sy: &Array2<Complex<F>>,
+    scale: F,
+    nfft: usize,
+    sides: Sides,
+    average: Average,
+) -> Array1<Complex<F>>
+where
+    F: Float + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+{
+    let mut pxy = Zip::from(sx).and(sy).map_collect(|x, y| (x.conj() * y).scale(scale));
+    if sides == Sides::OneSided {
+        pxy.rows_mut()
+            .into_iter()
+            .for_each(|row| double_one_sided(row, nfft));
+    }
+    // The median is taken of the real and imaginary parts separately
+    let re = average.reduce(&pxy.mapv(|v| v.re));
+    let im = average.reduce(&pxy.mapv(|v| v.im));
+    Zip::from(&re).and(&im).map_collect(|&re, &im| Complex::new(re, im))
+}
+
+/// The window and the `segments x bins` spectra of the segments of two signals
+type SegmentSpectra<F> = (Vec<F>, Array2<Complex<F>>, Array2<Complex<F>>);
+
+/// Checks the signals and segmentation and returns the window and the spectra
+/// of the segments of both signals
+fn spectra<F, I, J>(
+    x: &I,
+    y: &J,
+    fs: F,
+    segments: &Segments<F>,
+    sides: Sides,
+) -> Result<SegmentSpectra<F>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    J: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    segments.check(x.len())?;
+    if y.len() != x.len() {
+        return Err(RufftError::LengthMismatch { expected: x.len(), actual: y.len() });
+    }
+    check_fs(fs)?;
+    let x: Vec<F> = x.samples().cloned().collect();
+    let y: Vec<F> = y.samples().cloned().collect();
+    let win: Vec<F> = get_window(segments.window, segments.nperseg, Symmetry::Periodic);
+    let sx = segment_spectra(&x, segments, &win, sides);
+    let sy = segment_spectra(&y, segments, &win, sides);
+    Ok((win, sx, sy))
+}
+
+/// Estimates the cross power spectral density, or cross power spectrum, of the
+/// real valued input signals of the same length sampled at `fs` by Welch's
+/// method. Returns the frequencies of the bins and the averaged cross spectra
+/// `conj(X) Y` of the segments
+pub fn csd<F, I, J>(
+    x: &I,
+    y: &J,
+    fs: F,
+    segments: &Segments<F>,
+    sides: Sides,
+    scaling: Scaling,
+    average: Average,
+) -> Result<(Array1<F>, Array1<Complex<F>>), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    J: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    let (win, sx, sy) = spectra(x, y, fs, segments, sides)?;
+    let pxy = cross_spectrum(&sx, &sy, scaling.factor(&win, fs), segments.nfft, sides, average);
+    Ok((frequencies(segments.nfft, fs, sides), pxy))
+}
+
+/// Estimates the magnitude-squared coherence `|Pxy|² / (Pxx Pyy)` of the real
+/// valued input signals of the same length sampled at `fs` from the mean
+/// one-sided spectral densities. Returns the frequencies of the bins and the
+/// coherence, which is between zero and one
+pub fn coherence<F, I, J>(
+    x: &I,
+    y: &J,
+    fs: F,
+    segments: &Segments<F>,
+) -> Result<(Array1<F>, Array1<F>), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    J: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    let sides = Sides::OneSided;
+    let (win, sx, sy) = spectra(x, y, fs, segments, sides)?;
+    let scale = Scaling::Density.factor(&win, fs);
+    let nfft = segments.nfft;
+    let pxx = cross_spectrum(&sx, &sx, scale, nfft, sides, Average::Mean);
+    let pyy = cross_spectrum(&sy, &sy, scale, nfft, sides, Average::Mean);
+    let pxy = cross_spectrum(&sx, &sy, scale, nfft, sides, Average::Mean);
+    let cxy = Zip::from(&pxy)
+        .and(&pxx)
+        .and(&pyy)
+        .map_collect(|pxy, pxx, pyy| pxy.norm_sqr() / (pxx.re * pyy.re));
+    Ok((frequencies(nfft, fs, sides), cxy))
+}
+
+/// Estimates the frequency response of a system with the real valued input
+/// signal `x` and output signal `y` of the same length sampled at `fs` from
+/// the mean spectral densities. Returns the frequencies of the bins and the
+/// complex valued transfer function
+pub fn transfer_function<F, I, J>(
+    x: &I,
+    y: &J,
+    fs: F,
+    segments: &Segments<F>,
+    sides: Sides,
+    estimator: Estimator,
+) -> Result<(Array1<F>, Array1<Complex<F>>), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    J: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    let (win, sx, sy) = spectra(x, y, fs, segments, sides)?;
+    // The scaling and one-sided doubling cancel in the ratios
+    let scale = Scaling::Density.factor(&win, fs);
+    let nfft = segments.nfft;
+    let pxy = cross_spectrum(&sx, &sy, scale, nfft, sides, Average::Mean);
+    let h = match estimator {
+        Estimator::H1 => {
+            let pxx = cross_spectrum(&sx, &sx, scale, nfft, sides, Average::Mean);
+            Zip::from(&pxy).and(&pxx).map_collect(|&pxy, pxx| pxy.unscale(pxx.re))
+        },
+        Estimator::H2 => {
+            let pyy = cross_spectrum(&sy, &sy, scale, nfft, sides, Average::Mean);
+            Zip::from(&pyy).and(&pxy).map_collect(|pyy, pxy| pxy.conj().inv().scale(pyy.re))
+        },
+    };
+    Ok((frequencies(nfft, fs, sides), h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::itertools::window::Window;
+    use crate::spectral::{ welch, Detrend };
+    use crate::test_utils::{ self as test, read_json, Data, Json };
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    /// A white noise like input and the output of a two tap filter with a
+    /// small amount of uncorrelated noise
+    fn system(n: usize) -> (Vec<f64>, Vec<f64>) {
+        // The splitmix64 hash of the index
+        let noise = |i: u64, seed: u64| {
+            let mut h = (i ^ seed).wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15);
+            h = (h ^ (h >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            h = (h ^ (h >> 27)).wrapping_mul(0x94D049BB133111EB);
+            h ^= h >> 31;
+            (h >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        };
+        let x: Vec<f64> = (0..n as u64).map(|i| noise(i, 0)).collect();
+        let y = (0..n)
+            .map(|i| 0.5 * x[i] + if i > 0 { 0.3 * x[i - 1] } else { 0.0 } + 0.01 * noise(i as u64, 0xABCD))
+            .collect();
+        (x, y)
+    }
+
+    /// Reads the signals, sampling frequency and segmentation with the hann
+    /// window and the given detrending of a scipy dataset
+    fn read_data(json_data: &Json<f64>, detrend: Detrend) -> (Vec<f64>, Vec<f64>, f64, Segments<f64>) {
+        match &json_data.input_data {
+            Data::CrossSegmentVals { x, y, fs, nperseg, noverlap, nfft } => {
+                let segments = Segments {
+                    window: Window::Hann,
+                    nperseg: *nperseg as usize,
+                    noverlap: *noverlap as usize,
+                    nfft: *nfft as usize,
+                    detrend,
+                };
+                (x.clone(), y.clone(), *fs, segments)
+            },
+            _ => panic!("Read the input data incorrectly")
+        }
+    }
+
+    fn test_csd_data(path: &str, detrend: Detrend, sides: Sides, scaling: Scaling, average: Average) {
+        let json_data: Json<f64> = read_json(path);
+        let (x, y, fs, segments) = read_data(&json_data, detrend);
+        let (freqs, pxy) = csd(&x, &y, fs, &segments, sides, scaling, average).unwrap();
+        assert_eq!(freqs.len(), pxy.len());
+        match json_data.output_data {
+            Data::ComplexVals { mag, phase } => {
+                assert_eq!(pxy.len(), mag.len());
+                for (i, (&output, (&m, &p))) in pxy.iter().zip(mag.iter().zip(phase.iter())).enumerate() {
+                    let reference = Complex::from_polar(m, p);
+                    assert!(test::nearly_equal_complex(output, reference, RTOL_F64, ATOL_F64),
+                        "{} => {} != {}", i, output, reference);
+                }
+            },
+            _ => panic!("Read the output data incorrectly")
+        }
+    }
+
+    #[test]
+    fn test_csd() {
+        test_csd_data(
+            "datasets/spectral/csd/csd.json",
+            Detrend::Constant, Sides::OneSided, Scaling::Density, Average::Mean
+        );
+    }
+
+    #[test]
+    fn test_csd_median_two_sided() {
+        test_csd_data(
+            "datasets/spectral/csd_median_two_sided/csd_median_two_sided.json",
+            Detrend::Linear, Sides::TwoSided, Scaling::Spectrum, Average::Median
+        );
+    }
+
+    #[test]
+    fn test_coherence() {
+        let json_data: Json<f64> = read_json("datasets/spectral/coherence/coherence.json");
+        let (x, y, fs, segments) = read_data(&json_data, Detrend::Constant);
+        let (freqs, cxy) = coherence(&x, &y, fs, &segments).unwrap();
+        assert_eq!(freqs.len(), cxy.len());
+        match json_data.output_data {
+            Data::Array(reference) => {
+                assert_eq!(cxy.len(), reference.len());
+                for (i, (&a, &b)) in cxy.iter().zip(reference.iter()).enumerate() {
+                    assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+                }
+            },
+            _ => panic!("Read the output data incorrectly")
+        }
+    }
+
+    #[test]
+    fn test_csd_matches_welch() {
+        let (x, _) = system(1000);
+        let segments = Segments::new(Window::Hann, 100);
+        for sides in [Sides::OneSided, Sides::TwoSided] {
+            for average in [Average::Mean, Average::Median] {
+                let (_, pxx) = welch(&x, 2.0, &segments, sides, Scaling::Density, average).unwrap();
+                let (_, pxy) = csd(&x, &x, 2.0, &segments, sides, Scaling::Density, average).unwrap();
+                for (&a, &b) in pxy.iter().zip(pxx.iter()) {
+                    assert!(test::nearly_equal(a.re, b, RTOL_F64, ATOL_F64));
+                    assert!(a.im.abs() < ATOL_F64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transfer_function() {
+        let fs = 100.0;
+        let (x, y) = system(8192);
+        // Removing the mean of the segments would remove the zero frequency response
+        let segments = Segments { detrend: Detrend::None, ..Segments::new(Window::Hann, 256) };
+        let (freqs, h1) = transfer_function(&x, &y, fs, &segments, Sides::OneSided, Estimator::H1).unwrap();
+        let (_, h2) = transfer_function(&x, &y, fs, &segments, Sides::OneSided, Estimator::H2).unwrap();
+        let (_, cxy) = coherence(&x, &y, fs, &segments).unwrap();
+        for (i, &f) in freqs.iter().enumerate() {
+            // The frequency response of the filter 0.5 + 0.3 z^-1
+            let expected = Complex::new(0.5, 0.0) + Complex::from_polar(0.3, -core::f64::consts::TAU * f / fs);
+            assert!((h1[i] - expected).norm() < 0.02 * expected.norm(), "{} => {} != {}", f, h1[i], expected);
+            assert!((h2[i] - expected).norm() < 0.02 * expected.norm(), "{} => {} != {}", f, h2[i], expected);
+            // H1 and H2 bound the response, their ratio is the coherence
+            assert!(test::nearly_equal((h1[i] / h2[i]).re, cxy[i], RTOL_F64, ATOL_F64));
+            assert!(cxy[i] > 0.95 && cxy[i] <= 1.0 + ATOL_F64);
+        }
+
+        // A gain is recovered exactly
+        let y: Vec<f64> = x.iter().map(|v| 2.5 * v).collect();
+        for estimator in [Estimator::H1, Estimator::H2] {
+            let (_, h) = transfer_function(&x, &y, fs, &segments, Sides::TwoSided, estimator).unwrap();
+            assert!(h.iter().all(|v| (v - 2.5).norm() < ATOL_F64));
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let (x, y) = system(100);
+        let segments = Segments::new(Window::Hann, 32);
+        assert_eq!(
+            csd(&x, &y[..50], 1.0, &segments, Sides::OneSided, Scaling::Density, Average::Mean).unwrap_err(),
+            RufftError::LengthMismatch { expected: 100, actual: 50 }
+        );
+        assert_eq!(coherence(&x[..0], &y[..0], 1.0, &segments).unwrap_err(), RufftError::EmptyInput);
+        assert_eq!(
+            transfer_function(&x, &y, 0.0, &segments, Sides::OneSided, Estimator::H1).unwrap_err(),
+            RufftError::InvalidParameter("fs")
+        );
+    }
+}
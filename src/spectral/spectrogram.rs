@@ -0,0 +1,184 @@
+//! Spectrograms of consecutive segments of a signal, equivalent to
+//! `scipy.signal.spectrogram` without boundary extension or padding, so only
+//! complete segments are transformed.
+use ndarray::Array2;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::itertools::window::{ get_window, Symmetry };
+use crate::spectral::{
+    check_fs, double_one_sided, frequencies, segment_spectra, Scaling, Segments, Sides, TimeFrequency
+};
+use crate::traits::Signal;
+
+/// The value of each bin of a spectrogram
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// The power spectral density or power spectrum, depending on the scaling.
+    /// One-sided spectra include the power of the negative frequencies
+    Psd,
+    /// The magnitude of the spectrum scaled by the square root of the scaling
+    /// factor, the bins of one-sided spectra are not doubled
+    Magnitude,
+    /// The power of `Psd` in decibels, `10 log10(psd)`
+    Decibels,
+}
+
+/// Computes the spectrogram of the real valued input signal sampled at `fs`.
+/// Returns the frequencies of the bins, the times of the segment centres and
+/// the `segments x bins` array of the spectrogram in the given mode
+pub fn spectrogram<F, I>(
+    x: &I,
+    fs: F,
+    segments: &Segments<F>,
+    sides: Sides,
+    scaling: Scaling,
+    mode: Mode,
+) -> Result<TimeFrequency<F, F>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    segments.check(x.len())?;
+    check_fs(fs)?;
+    let x: Vec<F> = x.samples().cloned().collect();
+    let win: Vec<F> = get_window(segments.window, segments.nperseg, Symmetry::Periodic);
+    let spectra = segment_spectra(&x, segments, &win, sides);
+    let scale = scaling.factor(&win, fs);
+
+    let mut sxx: Array2<F> = match mode {
+        Mode::Magnitude => spectra.mapv(|v| v.norm() * scale.sqrt()),
+        Mode::Psd | Mode::Decibels => spectra.mapv(|v| v.norm_sqr() * scale),
+    };
+    if mode != Mode::Magnitude && sides == Sides::OneSided {
+        sxx.rows_mut()
+            .into_iter()
+            .for_each(|row| double_one_sided(row, segments.nfft));
+    }
+    if mode == Mode::Decibels {
+        let ten: F = 10.0.as_();
+        sxx.mapv_inplace(|v| ten * v.log10());
+    }
+
+    let half = segments.nperseg.as_() / (F::one() + F::one());
+    let times = (0..sxx.nrows())
+        .map(|i| (half + (i * segments.step()).as_()) / fs)
+        .collect();
+    Ok((frequencies(segments.nfft, fs, sides), times, sxx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::itertools::window::Window;
+    use crate::spectral::Detrend;
+    use crate::test_utils::{ self as test, read_json, Data, Json };
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    /// Compares the spectrogram against the scipy dataset computed with the
+    /// hann window and the given options
+    fn test_spectrogram_data(path: &str, detrend: Detrend, sides: Sides, scaling: Scaling, mode: Mode) {
+        let json_data: Json<f64> = read_json(path);
+        let (x, fs, segments) = match json_data.input_data {
+            Data::SegmentVals { x, fs, nperseg, noverlap, nfft } => {
+                let segments = Segments {
+                    window: Window::Hann,
+                    nperseg: nperseg as usize,
+                    noverlap: noverlap as usize,
+                    nfft: nfft as usize,
+                    detrend,
+                };
+                (x, fs, segments)
+            },
+            _ => panic!("Read the input data incorrectly")
+        };
+        let (freqs, times, sxx) = spectrogram(&x, fs, &segments, sides, scaling, mode).unwrap();
+        assert_eq!(sxx.dim(), (segments.count(x.len()), freqs.len()));
+        assert_eq!(times.len(), sxx.nrows());
+        assert!(test::nearly_equal(times[0], segments.nperseg as f64 / 2.0 / fs, RTOL_F64, ATOL_F64));
+        match json_data.output_data {
+            Data::Array(reference) => {
+                assert_eq!(sxx.len(), reference.len());
+                for (i, (&output, &reference)) in sxx.iter().zip(reference.iter()).enumerate() {
+                    assert!(test::nearly_equal(output, reference, RTOL_F64, ATOL_F64),
+                        "{} => {} != {}", i, output, reference);
+                }
+            },
+            _ => panic!("Read the output data incorrectly")
+        }
+    }
+
+    #[test]
+    fn test_spectrogram_psd() {
+        test_spectrogram_data(
+            "datasets/spectral/spectrogram_psd/spectrogram_psd.json",
+            Detrend::Constant, Sides::OneSided, Scaling::Density, Mode::Psd
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_spectrum() {
+        test_spectrogram_data(
+            "datasets/spectral/spectrogram_spectrum/spectrogram_spectrum.json",
+            Detrend::None, Sides::OneSided, Scaling::Spectrum, Mode::Psd
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_magnitude() {
+        test_spectrogram_data(
+            "datasets/spectral/spectrogram_magnitude/spectrogram_magnitude.json",
+            Detrend::Constant, Sides::OneSided, Scaling::Density, Mode::Magnitude
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_two_sided() {
+        test_spectrogram_data(
+            "datasets/spectral/spectrogram_two_sided/spectrogram_two_sided.json",
+            Detrend::Linear, Sides::TwoSided, Scaling::Density, Mode::Psd
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_decibels() {
+        let x: Vec<f64> = (0..200).map(|i| (0.4 * i as f64).sin() + 0.3).collect();
+        let segments = Segments::new(Window::Hann, 40);
+        let (_, _, psd) = spectrogram(&x, 8.0, &segments, Sides::OneSided, Scaling::Spectrum, Mode::Psd).unwrap();
+        let (_, _, db) = spectrogram(&x, 8.0, &segments, Sides::OneSided, Scaling::Spectrum, Mode::Decibels).unwrap();
+        for (&p, &d) in psd.iter().zip(db.iter()) {
+            assert!(test::nearly_equal(d, 10.0 * p.log10(), RTOL_F64, ATOL_F64));
+        }
+    }
+
+    #[test]
+    fn test_spectrogram_sine_power() {
+        // A bin centred sinusoid of amplitude 2 has a power of 2 in its bin
+        let fs = 64.0;
+        let x: Vec<f64> = (0..256).map(|i| 2.0 * (core::f64::consts::TAU * 8.0 * i as f64 / fs).sin()).collect();
+        let segments = Segments { detrend: Detrend::None, ..Segments::new(Window::Boxcar, 64) };
+        let (freqs, _, sxx) = spectrogram(&x, fs, &segments, Sides::OneSided, Scaling::Spectrum, Mode::Psd).unwrap();
+        assert_eq!(freqs[8], 8.0);
+        for row in sxx.rows() {
+            assert!(test::nearly_equal(row[8], 2.0, RTOL_F64, ATOL_F64));
+            assert!(test::nearly_equal(row.sum(), 2.0, RTOL_F64, ATOL_F64));
+        }
+    }
+
+    #[test]
+    fn test_spectrogram_invalid_parameters() {
+        let x = vec![1.0; 32];
+        let segments = Segments::new(Window::Hann, 16);
+        let run = |x: &[f64], fs: f64, segments: &Segments<f64>| {
+            spectrogram(x, fs, segments, Sides::OneSided, Scaling::Density, Mode::Psd).unwrap_err()
+        };
+        assert_eq!(run(&x[..0], 1.0, &segments), RufftError::EmptyInput);
+        assert_eq!(run(&x, -1.0, &segments), RufftError::InvalidParameter("fs"));
+        assert_eq!(run(&x, 1.0, &Segments { noverlap: 16, ..segments }), RufftError::InvalidParameter("noverlap"));
+        assert_eq!(run(&x, 1.0, &Segments { nperseg: 64, nfft: 64, ..segments }), RufftError::InvalidParameter("nperseg"));
+    }
+}
@@ -0,0 +1,302 @@
+//! The short time fourier transform and its inverse by weighted overlap-add.
+//!
+//! The signal is extended by `nperseg / 2` zeros at both ends so the first and
+//! last segments are centred on the first and last samples, and zero padded at
+//! the end so the final segment is complete, as with `scipy.signal.stft`'s
+//! default `boundary="zeros"` and `padded=True`. The window is always used in
+//! its periodic form and each segment's spectrum is scaled by the inverse of
+//! the window sum.
+use ndarray::{ Array1, Array2, ArrayBase, Data, Ix2 };
+use num_complex::Complex;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::fft::real::{ rfft_into, rfft_scratch_len, irfft_into, irfft_scratch_len };
+use crate::itertools::window::{ get_window, Symmetry, Window };
+use crate::spectral::{ check_fs, check_segments, rfftfreq, TimeFrequency };
+use crate::traits::Signal;
+
+/// Tolerance of the COLA and NOLA checks, and the smallest window sum squared
+/// the inverse transform divides by
+const TOL: f64 = 1e-10;
+
+/// Sums the `nperseg / step` consecutive bins of `step` samples of the
+/// window, wrapping the remainder of the window around to the first bins. The
+/// sums are the values of the overlapped windows in the steady state
+fn binsums<F: Float>(win: &[F], step: usize) -> Vec<F> {
+    let mut sums = vec![F::zero(); step];
+    win.chunks(step).for_each(|chunk| {
+        sums.iter_mut().zip(chunk.iter()).for_each(|(s, &w)| *s = *s + w);
+    });
+    sums
+}
+
+/// Returns true if the overlapped windows satisfy the nonzero overlap-add
+/// constraint, so every sample is covered by a window
+fn nola<F>(win: &[F], step: usize) -> bool
+where
+    F: Float + 'static,
+    f64: AsPrimitive<F>,
+{
+    let squares: Vec<F> = win.iter().map(|&w| w * w).collect();
+    binsums(&squares, step)
+        .into_iter()
+        .all(|s| s > TOL.as_())
+}
+
+/// Returns true if the segments of the window with the given overlap satisfy
+/// the constant overlap-add constraint, i.e. the overlapped windows sum to a
+/// constant. The window is used in its periodic form
+pub fn check_cola<F>(window: Window<F>, nperseg: usize, noverlap: usize) -> Result<bool, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    check_segments(nperseg, noverlap, nperseg)?;
+    let win: Vec<F> = get_window(window, nperseg, Symmetry::Periodic);
+    let mut sums = binsums(&win, nperseg - noverlap);
+    sums.sort_by(|a, b| a.partial_cmp(b).expect("The window has no NaN samples"));
+    let mid = sums.len() / 2;
+    let median = if sums.len() % 2 == 1 {
+        sums[mid]
+    } else {
+        (sums[mid - 1] + sums[mid]) / (F::one() + F::one())
+    };
+    Ok(sums.iter().all(|&s| (s - median).abs() < TOL.as_()))
+}
+
+/// Returns true if the segments of the window with the given overlap satisfy
+/// the nonzero overlap-add constraint, which is required to invert the short
+/// time fourier transform with `istft`. The window is used in its periodic form
+pub fn check_nola<F>(window: Window<F>, nperseg: usize, noverlap: usize) -> Result<bool, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    check_segments(nperseg, noverlap, nperseg)?;
+    let win: Vec<F> = get_window(window, nperseg, Symmetry::Periodic);
+    Ok(nola(&win, nperseg - noverlap))
+}
+
+/// Computes the short time fourier transform of the real valued input signal
+/// sampled at `fs` with segments of `nperseg` samples overlapping by
+/// `noverlap` samples, each zero padded to `nfft` samples. Returns the
+/// frequencies of the `nfft / 2 + 1` bins, the times of the segment centres
+/// and the `segments x bins` array of spectra
+pub fn stft<F, I>(
+    x: &I,
+    fs: F,
+    window: Window<F>,
+    nperseg: usize,
+    noverlap: usize,
+    nfft: usize,
+) -> Result<TimeFrequency<F, Complex<F>>, RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    let n = x.len();
+    if n == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    check_segments(nperseg, noverlap, nfft)?;
+    check_fs(fs)?;
+    if nperseg > n {
+        return Err(RufftError::InvalidParameter("nperseg"));
+    }
+
+    let win: Vec<F> = get_window(window, nperseg, Symmetry::Periodic);
+    let step = nperseg - noverlap;
+    let half = nperseg / 2;
+    let rem = (n + 2 * half - nperseg) % step;
+    let pad = if rem == 0 { 0 } else { step - rem };
+    let padded: Vec<F> = core::iter::repeat_n(F::zero(), half)
+        .chain(x.samples().cloned())
+        .chain(core::iter::repeat_n(F::zero(), half + pad))
+        .collect();
+    let segments = (padded.len() - noverlap) / step;
+
+    let scale = F::one() / win.iter().fold(F::zero(), |acc, &w| acc + w);
+    let mut zxx = Array2::zeros((segments, nfft / 2 + 1));
+    let mut buf = vec![F::zero(); nfft];
+    let mut scratch = vec![Complex::new(F::zero(), F::zero()); rfft_scratch_len(nfft)];
+    for (mut row, segment) in zxx.rows_mut().into_iter().zip(padded.windows(nperseg).step_by(step)) {
+        buf.iter_mut()
+            .zip(segment.iter().zip(win.iter()))
+            .for_each(|(b, (&v, &w))| *b = v * w);
+        rfft_into(&buf, row.as_slice_mut().expect("The array is in standard layout"), &mut scratch)?;
+        row.mapv_inplace(|v| v.scale(scale));
+    }
+
+    let times = (0..segments).map(|i| (i * step).as_() / fs).collect();
+    Ok((rfftfreq(nfft, fs), times, zxx))
+}
+
+/// Computes the inverse short time fourier transform of the `segments x bins`
+/// array of spectra returned by `stft` with the same parameters, by weighted
+/// overlap-add of the inverse transforms of the segments. Returns the sample
+/// times and the reconstructed signal, which includes any zero padding added
+/// to complete the final segment. The window and overlap must satisfy the
+/// nonzero overlap-add constraint, see `check_nola`
+pub fn istft<F, S>(
+    zxx: &ArrayBase<S, Ix2>,
+    fs: F,
+    window: Window<F>,
+    nperseg: usize,
+    noverlap: usize,
+    nfft: usize,
+) -> Result<(Array1<F>, Array1<F>), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    S: Data<Elem = Complex<F>>,
+    usize: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    let (segments, bins) = zxx.dim();
+    if segments == 0 {
+        return Err(RufftError::EmptyInput);
+    }
+    check_segments(nperseg, noverlap, nfft)?;
+    check_fs(fs)?;
+    if bins != nfft / 2 + 1 {
+        return Err(RufftError::LengthMismatch { expected: nfft / 2 + 1, actual: bins });
+    }
+    let win: Vec<F> = get_window(window, nperseg, Symmetry::Periodic);
+    let step = nperseg - noverlap;
+    if !nola(&win, step) {
+        return Err(RufftError::InvalidParameter("noverlap"));
+    }
+
+    let len = nperseg + (segments - 1) * step;
+    let mut x = vec![F::zero(); len];
+    let mut norm = vec![F::zero(); len];
+    let sum = win.iter().fold(F::zero(), |acc, &w| acc + w);
+    let mut spectrum = vec![Complex::new(F::zero(), F::zero()); bins];
+    let mut buf = vec![F::zero(); nfft];
+    let mut scratch = vec![Complex::new(F::zero(), F::zero()); irfft_scratch_len(nfft)];
+    for (i, row) in zxx.rows().into_iter().enumerate() {
+        spectrum.iter_mut().zip(row.iter()).for_each(|(s, &v)| *s = v);
+        irfft_into(&spectrum, &mut buf, &mut scratch)?;
+        let start = i * step;
+        for (j, &w) in win.iter().enumerate() {
+            // Undo the scaling of the forward transform and apply the synthesis window
+            x[start + j] += buf[j] * sum * w;
+            norm[start + j] += w * w;
+        }
+    }
+
+    // Remove the boundary extension
+    let half = nperseg / 2;
+    let signal: Array1<F> = x[half..len - half]
+        .iter()
+        .zip(norm[half..len - half].iter())
+        .map(|(&v, &s)| if s > TOL.as_() { v / s } else { v })
+        .collect();
+    let times = (0..signal.len()).map(|i| i.as_() / fs).collect();
+    Ok((times, signal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ self as test, read_json, real_signal, Data, Json };
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    /// Compares the short time fourier transform against the scipy dataset
+    fn test_stft_data(path: &str) {
+        let json_data: Json<f64> = read_json(path);
+        let (x, fs, nperseg, noverlap, nfft) = match json_data.input_data {
+            Data::SegmentVals { x, fs, nperseg, noverlap, nfft } => {
+                (x, fs, nperseg as usize, noverlap as usize, nfft as usize)
+            },
+            _ => panic!("Read the input data incorrectly")
+        };
+        let (freqs, times, zxx) = stft(&x, fs, Window::Hann, nperseg, noverlap, nfft).unwrap();
+        assert_eq!(freqs.len(), nfft / 2 + 1);
+        for (k, &f) in freqs.iter().enumerate() {
+            assert!(test::nearly_equal(f, k as f64 * fs / nfft as f64, RTOL_F64, ATOL_F64));
+        }
+        for (i, &t) in times.iter().enumerate() {
+            assert!(test::nearly_equal(t, (i * (nperseg - noverlap)) as f64 / fs, RTOL_F64, ATOL_F64));
+        }
+        match json_data.output_data {
+            Data::ComplexVals { mag, phase } => {
+                assert_eq!(zxx.len(), mag.len());
+                for (i, (&output, (&m, &p))) in zxx.iter().zip(mag.iter().zip(phase.iter())).enumerate() {
+                    let reference = Complex::from_polar(m, p);
+                    assert!(test::nearly_equal_complex(output, reference, RTOL_F64, ATOL_F64),
+                        "{} => {} != {}", i, output, reference);
+                }
+            },
+            _ => panic!("Read the output data incorrectly")
+        }
+    }
+
+    #[test]
+    fn test_stft() {
+        test_stft_data("datasets/spectral/stft/stft.json");
+        test_stft_data("datasets/spectral/stft_odd/stft_odd.json");
+    }
+
+    #[test]
+    fn test_istft_roundtrip() {
+        let x = real_signal(300);
+        for (window, nperseg, noverlap, nfft) in [
+            (Window::Hann, 64, 48, 64),
+            (Window::Hann, 32, 16, 45),
+            (Window::Hamming, 50, 30, 50),
+            (Window::Boxcar, 16, 0, 16),
+        ] {
+            let (_, _, zxx) = stft(&x, 10.0, window, nperseg, noverlap, nfft).unwrap();
+            let (times, y) = istft(&zxx, 10.0, window, nperseg, noverlap, nfft).unwrap();
+            assert!(y.len() >= x.len());
+            assert_eq!(times.len(), y.len());
+            assert!(test::nearly_equal(times[1], 0.1, RTOL_F64, ATOL_F64));
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+            }
+            // The padding of the final segment is reconstructed as zeros
+            assert!(y.iter().skip(x.len()).all(|v| v.abs() < ATOL_F64));
+        }
+    }
+
+    #[test]
+    fn test_check_cola() {
+        assert_eq!(check_cola(Window::<f64>::Hann, 64, 32), Ok(true));
+        assert_eq!(check_cola(Window::<f64>::Hann, 64, 48), Ok(true));
+        assert_eq!(check_cola(Window::<f64>::Boxcar, 64, 0), Ok(true));
+        assert_eq!(check_cola(Window::<f64>::Hann, 64, 10), Ok(false));
+        assert_eq!(check_cola(Window::<f64>::Hann, 64, 64), Err(RufftError::InvalidParameter("noverlap")));
+    }
+
+    #[test]
+    fn test_check_nola() {
+        assert_eq!(check_nola(Window::<f64>::Hann, 64, 10), Ok(true));
+        // The periodic hann window is zero at its first sample
+        assert_eq!(check_nola(Window::<f64>::Hann, 64, 0), Ok(false));
+        assert_eq!(check_nola(Window::<f64>::Boxcar, 64, 0), Ok(true));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let x = real_signal(100);
+        assert_eq!(stft(&x[..0], 1.0, Window::Hann, 16, 8, 16).unwrap_err(), RufftError::EmptyInput);
+        assert_eq!(stft(&x, 1.0, Window::Hann, 0, 0, 16).unwrap_err(), RufftError::InvalidParameter("nperseg"));
+        assert_eq!(stft(&x, 1.0, Window::Hann, 128, 8, 128).unwrap_err(), RufftError::InvalidParameter("nperseg"));
+        assert_eq!(stft(&x, 1.0, Window::Hann, 16, 16, 16).unwrap_err(), RufftError::InvalidParameter("noverlap"));
+        assert_eq!(stft(&x, 1.0, Window::Hann, 16, 8, 8).unwrap_err(), RufftError::InvalidParameter("nfft"));
+        assert_eq!(stft(&x, 0.0, Window::Hann, 16, 8, 16).unwrap_err(), RufftError::InvalidParameter("fs"));
+
+        let (_, _, zxx) = stft(&x, 1.0, Window::Hann, 16, 8, 16).unwrap();
+        assert_eq!(
+            istft(&zxx, 1.0, Window::Hann, 16, 8, 20).unwrap_err(),
+            RufftError::LengthMismatch { expected: 11, actual: 9 }
+        );
+        assert_eq!(istft(&zxx, 1.0, Window::Hann, 16, 0, 16).unwrap_err(), RufftError::InvalidParameter("noverlap"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Power spectral density estimates by Welch's method, the average of the
+//! periodograms of overlapping windowed segments, and the periodogram of the
+//! whole signal. Averaging the segments reduces the variance of the estimate
+//! at the cost of frequency resolution.
+use ndarray::{ Array1, Array2, Axis };
+use num_integer::Integer;
+use num_traits::{ Float, FloatConst, NumAssign, AsPrimitive };
+use crate::error::RufftError;
+use crate::itertools::window::{ get_window, Symmetry, Window };
+use crate::spectral::{
+    check_fs, double_one_sided, frequencies, segment_spectra, Detrend, Scaling, Segments, Sides
+};
+use crate::traits::Signal;
+
+/// How the spectra of the segments are averaged, equivalent to the `average`
+/// argument of the scipy functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Average {
+    /// The mean of the segments
+    Mean,
+    /// The median of the segments divided by the bias of the median of
+    /// exponentially distributed values, which is robust to transients
+    Median,
+}
+
+impl Average {
+    /// Averages the rows of the `segments x bins` array
+    pub(crate) fn reduce<F>(self, x: &Array2<F>) -> Array1<F>
+    where
+        F: Float + 'static,
+        usize: AsPrimitive<F>,
+    {
+        let segments = x.nrows();
+        match self {
+            Self::Mean => {
+                let n: F = segments.as_();
+                x.sum_axis(Axis(0)).mapv(|v| v / n)
+            },
+            Self::Median => {
+                let bias = median_bias::<F>(segments);
+                x.axis_iter(Axis(1))
+                    .map(|bin| {
+                        let mut values = bin.to_vec();
+                        values.sort_by(|a, b| a.partial_cmp(b).expect("The spectra have no NaN values"));
+                        let mid = segments / 2;
+                        let median = if segments.is_odd() {
+                            values[mid]
+                        } else {
+                            (values[mid - 1] + values[mid]) / (F::one() + F::one())
+                        };
+                        median / bias
+                    })
+                    .collect()
+            },
+        }
+    }
+}
+
+/// The ratio of the median to the mean of `n` exponentially distributed
+/// values, `1 - 1/2 + 1/3 - ... ± 1/n` for odd `n`, one for a single value
+fn median_bias<F>(n: usize) -> F
+where
+    F: Float + 'static,
+    usize: AsPrimitive<F>,
+{
+    (1..=n.saturating_sub(1) / 2)
+        .map(|i| 2 * i)
+        .fold(F::one(), |acc, i| acc + F::one() / (i + 1).as_() - F::one() / i.as_())
+}
+
+/// Estimates the power spectral density, or the power spectrum, of the real
+/// valued input signal sampled at `fs` by Welch's method. Returns the
+/// frequencies of the bins and the averaged power of the segments
+pub fn welch<F, I>(
+    x: &I,
+    fs: F,
+    segments: &Segments<F>,
+    sides: Sides,
+    scaling: Scaling,
+    average: Average,
+) -> Result<(Array1<F>, Array1<F>), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    segments.check(x.len())?;
+    check_fs(fs)?;
+    let x: Vec<F> = x.samples().cloned().collect();
+    let win: Vec<F> = get_window(segments.window, segments.nperseg, Symmetry::Periodic);
+    let scale = scaling.factor(&win, fs);
+    let mut power = segment_spectra(&x, segments, &win, sides).mapv(|v| v.norm_sqr() * scale);
+    if sides == Sides::OneSided {
+        power.rows_mut()
+            .into_iter()
+            .for_each(|row| double_one_sided(row, segments.nfft));
+    }
+    Ok((frequencies(segments.nfft, fs, sides), average.reduce(&power)))
+}
+
+/// Estimates the power spectral density, or the power spectrum, of the real
+/// valued input signal sampled at `fs` from the spectrum of the whole signal
+/// multiplied by the window. The signal is zero padded to `nfft` samples, or
+/// truncated if it is longer. Returns the frequencies of the bins and the
+/// power of the signal
+pub fn periodogram<F, I>(
+    x: &I,
+    fs: F,
+    window: Window<F>,
+    nfft: usize,
+    detrend: Detrend,
+    sides: Sides,
+    scaling: Scaling,
+) -> Result<(Array1<F>, Array1<F>), RufftError>
+where
+    F: Float + FloatConst + NumAssign + 'static,
+    I: Signal<Sample = F> + ?Sized,
+    usize: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+{
+    if x.is_empty() {
+        return Err(RufftError::EmptyInput);
+    }
+    if nfft == 0 {
+        return Err(RufftError::InvalidParameter("nfft"));
+    }
+    let x: Vec<F> = x.samples().take(nfft).cloned().collect();
+    let segments = Segments { window, nperseg: x.len(), noverlap: 0, nfft, detrend };
+    welch(&x, fs, &segments, sides, scaling, Average::Mean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ self as test, read_json, Data, Json };
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    /// Reads the signal, sampling frequency and segmentation with the window
+    /// and detrending of a scipy dataset, and the reference estimate
+    fn read_data(path: &str, window: Window<f64>, detrend: Detrend) -> (Vec<f64>, f64, Segments<f64>, Vec<f64>) {
+        let json_data: Json<f64> = read_json(path);
+        let (x, fs, segments) = match json_data.input_data {
+            Data::SegmentVals { x, fs, nperseg, noverlap, nfft } => {
+                let segments = Segments {
+                    window,
+                    nperseg: nperseg as usize,
+                    noverlap: noverlap as usize,
+                    nfft: nfft as usize,
+                    detrend,
+                };
+                (x, fs, segments)
+            },
+            _ => panic!("Read the input data incorrectly")
+        };
+        let reference = match json_data.output_data {
+            Data::Array(reference) => reference,
+            _ => panic!("Read the output data incorrectly")
+        };
+        (x, fs, segments, reference)
+    }
+
+    fn assert_nearly_equal(output: &Array1<f64>, reference: &[f64]) {
+        assert_eq!(output.len(), reference.len());
+        for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+            assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+    }
+
+    fn test_welch_data(path: &str, detrend: Detrend, sides: Sides, scaling: Scaling, average: Average) {
+        let (x, fs, segments, reference) = read_data(path, Window::Hann, detrend);
+        let (freqs, pxx) = welch(&x, fs, &segments, sides, scaling, average).unwrap();
+        assert_eq!(freqs.len(), pxx.len());
+        assert_nearly_equal(&pxx, &reference);
+    }
+
+    fn test_periodogram_data(path: &str, window: Window<f64>, detrend: Detrend, sides: Sides, scaling: Scaling) {
+        let (x, fs, segments, reference) = read_data(path, window, detrend);
+        let (freqs, pxx) = periodogram(&x, fs, window, segments.nfft, detrend, sides, scaling).unwrap();
+        assert_eq!(freqs.len(), pxx.len());
+        assert_nearly_equal(&pxx, &reference);
+    }
+
+    #[test]
+    fn test_welch() {
+        test_welch_data(
+            "datasets/spectral/welch/welch.json",
+            Detrend::Constant, Sides::OneSided, Scaling::Density, Average::Mean
+        );
+    }
+
+    #[test]
+    fn test_welch_median() {
+        test_welch_data(
+            "datasets/spectral/welch_median/welch_median.json",
+            Detrend::Linear, Sides::OneSided, Scaling::Spectrum, Average::Median
+        );
+    }
+
+    #[test]
+    fn test_welch_two_sided() {
+        test_welch_data(
+            "datasets/spectral/welch_two_sided/welch_two_sided.json",
+            Detrend::None, Sides::TwoSided, Scaling::Density, Average::Mean
+        );
+    }
+
+    #[test]
+    fn test_periodogram() {
+        test_periodogram_data(
+            "datasets/spectral/periodogram/periodogram.json",
+            Window::Boxcar, Detrend::Constant, Sides::OneSided, Scaling::Density
+        );
+    }
+
+    #[test]
+    fn test_periodogram_truncated() {
+        test_periodogram_data(
+            "datasets/spectral/periodogram_truncated/periodogram_truncated.json",
+            Window::Hann, Detrend::Linear, Sides::TwoSided, Scaling::Spectrum
+        );
+    }
+
+    #[test]
+    fn test_welch_parseval() {
+        // The density integrates to the variance of white noise like signals
+        let x: Vec<f64> = (0..4096u64)
+            .map(|i| {
+                let h = i.wrapping_mul(0x9E3779B97F4A7C15) >> 11;
+                h as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect();
+        let fs = 10.0;
+        let (freqs, pxx) = periodogram(&x, fs, Window::Boxcar, x.len(), Detrend::None, Sides::OneSided, Scaling::Density).unwrap();
+        let df = freqs[1] - freqs[0];
+        let power = x.iter().map(|v| v * v).sum::<f64>() / x.len() as f64;
+        assert!(test::nearly_equal(pxx.sum() * df, power, RTOL_F64, ATOL_F64));
+    }
+
+    #[test]
+    fn test_median_bias() {
+        assert_eq!(median_bias::<f64>(1), 1.0);
+        assert_eq!(median_bias::<f64>(2), 1.0);
+        assert!(test::nearly_equal(median_bias::<f64>(5), 1.0 - 0.5 + 1.0 / 3.0 - 0.25 + 0.2, RTOL_F64, ATOL_F64));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let x = vec![1.0; 32];
+        assert_eq!(
+            periodogram(&x[..0], 1.0, Window::Boxcar, 32, Detrend::None, Sides::OneSided, Scaling::Density).unwrap_err(),
+            RufftError::EmptyInput
+        );
+        assert_eq!(
+            periodogram(&x, 1.0, Window::Boxcar, 0, Detrend::None, Sides::OneSided, Scaling::Density).unwrap_err(),
+            RufftError::InvalidParameter("nfft")
+        );
+        let segments = Segments::new(Window::Hann, 64);
+        assert_eq!(
+            welch(&x, 1.0, &segments, Sides::OneSided, Scaling::Density, Average::Mean).unwrap_err(),
+            RufftError::InvalidParameter("nperseg")
+        );
+    }
+}
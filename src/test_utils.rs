@@ -90,6 +90,28 @@ pub enum Data<T> {
         fsine: T,
         fsample: T,
         duration: T
+    },
+
+    WindowVals {
+        n: T,
+        param: T
+    },
+
+    SegmentVals {
+        x: Vec<T>,
+        fs: T,
+        nperseg: T,
+        noverlap: T,
+        nfft: T
+    },
+
+    CrossSegmentVals {
+        x: Vec<T>,
+        y: Vec<T>,
+        fs: T,
+        nperseg: T,
+        noverlap: T,
+        nfft: T
     }
 }
 
@@ -224,6 +246,26 @@ macro_rules! test_complex_ifourier_transform {
     };
 }
 
+macro_rules! test_trig_transform {
+    ($func:ident, $path:literal, $type:expr, $norm:expr, $F:ty, $I:ty, $rtol:expr, $atol:expr) => {
+        let json_data = crate::test_utils::read_json($path);
+        let output: $I = match json_data.input_data {
+            crate::test_utils::Data::<$F>::Array(input) => $func::<$F, $I, $I>(&(input.into()), $type, $norm),
+            _ => panic!("Read the input data incorrectly")
+        };
+        match json_data.output_data {
+            crate::test_utils::Data::Array(reference) => {
+                assert_eq!(output.len(), reference.len());
+                for i in 0..reference.len() {
+                    assert!(crate::test_utils::nearly_equal(output[i], reference[i], $rtol, $atol), 
+                        "{} => {} != {}", i, output[i], reference[i]);
+                }
+            }
+            _ => panic!("Read the output data incorrectly")
+        }
+    };
+}
+
 
 macro_rules! test_fft {
     ($F:ty,$I:ty, $C:ty, $rtol:expr, $atol:expr) => {
@@ -261,6 +303,12 @@ macro_rules! test_idft {
     };
 }
 
+macro_rules! test_ifft {
+    ($F:ty,$I:ty, $C:ty, $rtol:expr, $atol:expr) => {
+        crate::test_utils::test_ifourier_transform!(ifft, "datasets/fft/fft/fft.json", $F, $I, $C, $rtol, $atol);
+    };
+}
+
 
 pub(crate) use test_fft;
 pub(crate) use test_dft;
@@ -268,8 +316,34 @@ pub(crate) use test_complex_fft;
 pub(crate) use test_complex_dft;
 pub(crate) use test_complex_ifft;
 pub(crate) use test_idft;
+pub(crate) use test_ifft;
 
 pub(crate) use test_fourier_transform;
 pub(crate) use test_complex_fourier_transform;
 pub(crate) use test_complex_ifourier_transform;
-pub(crate) use test_ifourier_transform;
\ No newline at end of file
+pub(crate) use test_ifourier_transform;
+pub(crate) use test_trig_transform;
+
+/// Deterministic complex valued test signal `sin(0.3 i) + j cos(0.7 i)`
+pub fn signal<F: Float + 'static>(n: usize) -> Vec<Complex<F>>
+where
+    f64: AsPrimitive<F>
+{
+    (0..n).map(|i| Complex::new((i as f64 * 0.3).sin().as_(), (i as f64 * 0.7).cos().as_())).collect()
+}
+
+/// Real valued test signal `sin(0.3 i) + cos(0.7 i)`, the sum of the parts of
+/// `signal`
+pub fn real_signal(n: usize) -> Vec<f64> {
+    signal(n).into_iter().map(|v: Complex<f64>| v.re + v.im).collect()
+}
+
+/// Asserts that the output has the length of the reference and that each value
+/// is nearly equal with an absolute tolerance of `1e-10` and a relative 
+/// tolerance of `1e-9`
+pub fn assert_nearly_equal(output: &[Complex<f64>], reference: &[Complex<f64>]) {
+    assert_eq!(output.len(), reference.len());
+    for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+        assert!(nearly_equal_complex(a, b, 1e-9, 1e-10), "{} => {} != {}", i, a, b);
+    }
+}
@@ -1,11 +1,20 @@
+#[cfg(feature = "alloc")]
 use num_traits::{ NumAssign, Float, FloatConst, AsPrimitive };
+#[cfg(feature = "alloc")]
 use num_complex::Complex;
-use core::ops::{ IndexMut, Deref };
-use crate::fft;
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use alloc::{ boxed::Box, collections::VecDeque, vec::Vec };
+#[cfg(feature = "alloc")]
+use crate::error::RufftError;
+#[cfg(feature = "alloc")]
+use crate::fft::{ self, Normalization };
 
 /// Iterable trait to encapsulate collection types which have a length, are 
-/// reversible, and are iterable
-pub trait Iterable: FromIterator<Self::OwnedItem>
+/// reversible, and are iterable. Unlike the input side `Signal` trait, which
+/// every `Iterable` also implements, the collection can be constructed from an
+/// iterator so it is used for the output of transforms
+pub trait Iterable: FromIterator<Self::OwnedItem> + Signal<Sample = Self::OwnedItem>
 where 
     for<'c> Self::Item<'c>: Deref<Target = Self::OwnedItem>,
     for<'c> Self: 'c,
@@ -28,17 +37,19 @@ where
 
     /// Create an iterator from the collection over `Self::Item` types
     fn iter<'c>(&'c self) -> Self::Iterator<'c>;
+}
 
-    /// Return the length of the collection. Default implementation provided based
-    /// on the fact that an `ExactSizeIterator` implementation is required but 
-    /// may be more performant to override the implementation
-    #[inline]
-    fn len(&self) -> usize {
-        self.iter().len()
-    }
+/// Mutable counterpart to `Iterable` for collections which store their items
+/// contiguously, allowing algorithms to operate on the collection in-place
+pub trait IterableMut: Iterable
+where 
+    for<'c> Self::Item<'c>: Deref<Target = Self::OwnedItem>,
+{
+    /// Borrow the collection as a mutable slice
+    fn as_mut_slice(&mut self) -> &mut [Self::OwnedItem];
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 impl<T> Iterable for Vec<T>
 where 
     for<'c> T: 'c,
@@ -56,9 +67,16 @@ where
     fn iter<'c>(&'c self) -> Self::Iterator<'c> {
         self.as_slice().iter()
     }
+}
 
-    fn len(&self) -> usize {
-        self.len()
+#[cfg(feature = "alloc")]
+impl<T> IterableMut for Vec<T>
+where 
+    for<'c> T: 'c,
+    T: Clone,
+{
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        Vec::as_mut_slice(self)
     }
 }
 
@@ -79,6 +97,174 @@ where
     fn iter<'c>(&'c self) -> Self::Iterator<'c> {
         self.iter()
     }
+}
+
+#[cfg(all(feature = "ndarray", feature = "std"))]
+impl<T> IterableMut for ndarray::Array1<T>
+where
+    for<'c> T: 'c,
+    T: Clone,
+{
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        // Arrays which have been reversed or sliced with a step are not 
+        // contiguous so they're copied into standard layout first
+        if !self.is_standard_layout() {
+            *self = self.as_standard_layout().into_owned();
+        }
+        self.as_slice_mut().expect("Standard layout arrays are contiguous")
+    }
+}
+
+/// Input side collection trait for types which can be transformed without 
+/// being copied. Unlike `Iterable` it is not required to be constructible from
+/// an iterator or to be owned, so it is implemented for borrowed data such as 
+/// slices and `ndarray` views as well as for owned collections
+pub trait Signal {
+    /// The type of the samples in the collection. For example `f64` for `[f64]`
+    type Sample;
+
+    /// The iterator over references to the samples
+    type Samples<'s>: ExactSizeIterator<Item = &'s Self::Sample>
+        + DoubleEndedIterator<Item = &'s Self::Sample>
+    where
+        Self: 's;
+
+    /// Create an iterator over references to the samples of the collection
+    fn samples(&self) -> Self::Samples<'_>;
+
+    /// Return the number of samples in the collection
+    #[inline]
+    fn len(&self) -> usize {
+        self.samples().len()
+    }
+
+    /// Returns true if the collection has no samples
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Signal for [T] {
+    type Sample = T;
+    type Samples<'s> = core::slice::Iter<'s, T>
+    where
+        T: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+impl<T, const N: usize> Signal for [T; N] {
+    type Sample = T;
+    type Samples<'s> = core::slice::Iter<'s, T>
+    where
+        T: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
+
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Signal for Vec<T> {
+    type Sample = T;
+    type Samples<'s> = core::slice::Iter<'s, T>
+    where
+        T: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.as_slice().iter()
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Signal for Box<[T]> {
+    type Sample = T;
+    type Samples<'s> = core::slice::Iter<'s, T>
+    where
+        T: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Signal for VecDeque<T> {
+    type Sample = T;
+    type Samples<'s> = alloc::collections::vec_deque::Iter<'s, T>
+    where
+        T: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+}
+
+#[cfg(all(feature = "ndarray", feature = "std"))]
+impl<T> Signal for ndarray::Array1<T> {
+    type Sample = T;
+    type Samples<'s> = ndarray::iter::Iter<'s, T, ndarray::Ix1>
+    where
+        Self: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(all(feature = "ndarray", feature = "std"))]
+impl<'a, T> Signal for ndarray::ArrayView1<'a, T> {
+    type Sample = T;
+    type Samples<'s> = ndarray::iter::Iter<'s, T, ndarray::Ix1>
+    where
+        Self: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(all(feature = "ndarray", feature = "std"))]
+impl<'a, T> Signal for ndarray::ArrayViewMut1<'a, T> {
+    type Sample = T;
+    type Samples<'s> = ndarray::iter::Iter<'s, T, ndarray::Ix1>
+    where
+        Self: 's;
+
+    fn samples(&self) -> Self::Samples<'_> {
+        self.iter()
+    }
 
     fn len(&self) -> usize {
         self.len()
@@ -86,33 +272,450 @@ where
 }
 
 /// Trait containing `fft` method which computes the `fft` of the real valued
-/// collection type and returns a complex value collection. The return collection
-/// type does not need to be the same type as the type the trait is implemented
-/// on
+/// collection type and returns a complex value collection. It is implemented 
+/// for every `Signal` so borrowed data such as slices and array views can be 
+/// transformed directly. The return collection type does not need to be the 
+/// same type as the type the trait is implemented on. Nothing is cached between
+/// calls, see `fft::plan::FftPlanner` for transforming many collections
+#[cfg(feature = "alloc")]
 pub trait Fft<F: Float + FloatConst + NumAssign + 'static>
 where 
-    for<'c> Self: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    Self: Signal<Sample = F>,
     usize: AsPrimitive<F>,
 {   
     fn fft<C>(&self) -> C
     where 
         for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
-        C: IndexMut<usize, Output = Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        self.fft_norm(Normalization::Backward)
+    }
+
+    /// Computes the `fft` of the collection scaled according to the 
+    /// normalization mode
+    fn fft_norm<C>(&self, norm: Normalization) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
         usize: AsPrimitive<F>
     {
         let n = self.len();
         if n.is_power_of_two() {
-            fft::ct::fft::<F, Self, C>(self)
+            fft::ct::fft_norm::<F, Self, C>(self, norm)
+        } else if fft::mixed::is_supported_len(n) {
+            fft::mixed::fft_norm::<F, Self, C>(self, norm)
         } else {
-            fft::czt::fft::<F, Self, C>(self)
+            fft::czt::fft_norm::<F, Self, C>(self, norm)
         }
     }
+
+    /// Computes the length `n` `fft` of the collection which is zero padded or
+    /// truncated to length `n`. Equivalent to numpy's `fft(x, n)`
+    fn fft_n<C>(&self, n: usize) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        if n.is_power_of_two() {
+            fft::ct::fft_n::<F, Self, C>(self, n)
+        } else if fft::mixed::is_supported_len(n) {
+            fft::mixed::fft_n::<F, Self, C>(self, n)
+        } else {
+            fft::czt::fft_n::<F, Self, C>(self, n)
+        }
+    }
+
+    /// Fallible version of `fft`, returns an error if the collection is empty
+    fn try_fft<C>(&self) -> Result<C, RufftError>
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        if self.len() == 0 {
+            return Err(RufftError::EmptyInput);
+        }
+        Ok(self.fft())
+    }
+
+    /// Fallible version of `fft_n`, returns an error if `n` is zero
+    fn try_fft_n<C>(&self, n: usize) -> Result<C, RufftError>
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        if n == 0 {
+            return Err(RufftError::InvalidParameter("n"));
+        }
+        Ok(self.fft_n(n))
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<C, F> Fft<F> for C
 where 
-    for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+    C: Signal<Sample = F> + ?Sized,
     F: Float + FloatConst + NumAssign + 'static,
     usize: AsPrimitive<F>
 {}
 
+
+/// Trait containing `ifft` method which computes the inverse `fft` of the complex
+/// valued collection type and returns a real value collection. Like `Fft` it is
+/// implemented for every `Signal` of complex values so borrowed slices and array
+/// views can be inverse transformed. The complex valued inverse is computed in
+/// an intermediate buffer so the trait requires the `alloc` feature. The return
+/// collection type does not need to be the same type as the type the trait is
+/// implemented on.
+/// The output *is* normalized
+#[cfg(feature = "alloc")]
+pub trait Ifft<F: Float + FloatConst + NumAssign + 'static>
+where 
+    Self: Signal<Sample = Complex<F>>,
+    usize: AsPrimitive<F>,
+{   
+    fn ifft<C>(&self) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+        usize: AsPrimitive<F>
+    {
+        self.ifft_norm(Normalization::Backward)
+    }
+
+    /// Computes the inverse `fft` of the collection scaled according to the
+    /// normalization mode
+    fn ifft_norm<C>(&self, norm: Normalization) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+        usize: AsPrimitive<F>
+    {
+        let y: Vec<Complex<F>> = self.complex_ifft_norm(norm);
+        y.iter().map(|v| v.re).collect()
+    }
+
+    /// Fallible version of `ifft`, returns an error if the collection is empty
+    fn try_ifft<C>(&self) -> Result<C, RufftError>
+    where 
+        for<'c> C: Iterable<OwnedItem = F, Item<'c> = &'c F>,
+        usize: AsPrimitive<F>
+    {
+        if self.len() == 0 {
+            return Err(RufftError::EmptyInput);
+        }
+        Ok(self.ifft())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, F> Ifft<F> for C
+where 
+    C: Signal<Sample = Complex<F>> + ?Sized,
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{}
+
+/// Trait containing `complex_fft` method which computes the `fft` of the complex
+/// valued collection type and returns a complex valued collection. The transform
+/// algorithm is chosen based on the length of the collection in the same way as
+/// `Fft::fft`. The return collection type does not need to be the same type as
+/// the type the trait is implemented on
+#[cfg(feature = "alloc")]
+pub trait ComplexFft<F: Float + FloatConst + NumAssign + 'static>
+where 
+    Self: Signal<Sample = Complex<F>>,
+    usize: AsPrimitive<F>,
+{   
+    fn complex_fft<C>(&self) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        self.complex_fft_norm(Normalization::Backward)
+    }
+
+    /// Computes the `fft` of the collection scaled according to the 
+    /// normalization mode
+    fn complex_fft_norm<C>(&self, norm: Normalization) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        let n = self.len();
+        let mut y: C = self.samples().cloned().collect();
+        if n.is_power_of_two() {
+            fft::ct::complex::fft_in_place_norm(y.as_mut_slice(), norm);
+            y
+        } else if fft::mixed::is_supported_len(n) {
+            fft::mixed::complex::fft_norm(&y, norm)
+        } else {
+            fft::czt::complex::fft_norm(&y, norm)
+        }
+    }
+
+    /// Computes the length `n` `fft` of the collection which is zero padded or
+    /// truncated to length `n`. Equivalent to numpy's `fft(x, n)`
+    fn complex_fft_n<C>(&self, n: usize) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        let zero = Complex::new(F::zero(), F::zero());
+        let y: C = fft::resize(self.samples().cloned(), zero, n).collect();
+        y.complex_fft()
+    }
+
+    /// Fallible version of `complex_fft`, returns an error if the collection is
+    /// empty
+    fn try_complex_fft<C>(&self) -> Result<C, RufftError>
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        if self.len() == 0 {
+            return Err(RufftError::EmptyInput);
+        }
+        Ok(self.complex_fft())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, F> ComplexFft<F> for C
+where 
+    C: Signal<Sample = Complex<F>> + ?Sized,
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{}
+
+/// Trait containing `complex_ifft` method which computes the inverse `fft` of 
+/// the complex valued collection type and returns a complex valued collection.
+/// Unlike `Ifft::ifft` the imaginary part of the output is kept. The return 
+/// collection type does not need to be the same type as the type the trait is
+/// implemented on.
+/// The output *is* normalized
+#[cfg(feature = "alloc")]
+pub trait ComplexIfft<F: Float + FloatConst + NumAssign + 'static>
+where 
+    Self: Signal<Sample = Complex<F>>,
+    usize: AsPrimitive<F>,
+{   
+    fn complex_ifft<C>(&self) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        self.complex_ifft_norm(Normalization::Backward)
+    }
+
+    /// Computes the inverse `fft` of the collection scaled according to the
+    /// normalization mode
+    fn complex_ifft_norm<C>(&self, norm: Normalization) -> C
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        let n = self.len();
+        let mut y: C = self.samples().cloned().collect();
+        if n.is_power_of_two() {
+            fft::ct::complex::ifft_in_place_norm(y.as_mut_slice(), norm);
+            y
+        } else if fft::mixed::is_supported_len(n) {
+            fft::mixed::complex::ifft_norm(&y, norm)
+        } else {
+            fft::czt::complex::ifft_norm(&y, norm)
+        }
+    }
+
+    /// Fallible version of `complex_ifft`, returns an error if the collection 
+    /// is empty
+    fn try_complex_ifft<C>(&self) -> Result<C, RufftError>
+    where 
+        for<'c> C: Iterable<OwnedItem = Complex<F>, Item<'c> = &'c Complex<F>>,
+        C: IterableMut,
+        usize: AsPrimitive<F>
+    {
+        if self.len() == 0 {
+            return Err(RufftError::EmptyInput);
+        }
+        Ok(self.complex_ifft())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, F> ComplexIfft<F> for C
+where 
+    C: Signal<Sample = Complex<F>> + ?Sized,
+    F: Float + FloatConst + NumAssign + 'static,
+    usize: AsPrimitive<F>
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils as test;
+
+    const ATOL_F64: f64 = 1e-10;
+    const RTOL_F64: f64 = 1e-9;
+
+    #[test]
+    fn test_fft_ifft_roundtrip() {
+        for n in [16, 1000, 1001] {
+            let x: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin()).collect();
+            let spectrum: Vec<Complex<f64>> = x.fft();
+            let y: Vec<f64> = spectrum.ifft();
+            for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_n_pads_and_truncates() {
+        let x: Vec<f64> = (0..100).map(|i| (i as f64 * 0.3).sin()).collect();
+        for n in [64, 90, 97, 128, 150, 211] {
+            let output: Vec<Complex<f64>> = x.fft_n(n);
+            let resized: Vec<f64> = x.iter().cloned().chain(core::iter::repeat(0.0)).take(n).collect();
+            let reference: Vec<Complex<f64>> = fft::dft(&resized);
+            assert_eq!(output.len(), n);
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_fft_errors() {
+        let empty: Vec<f64> = vec![];
+        assert_eq!(empty.try_fft::<Vec<Complex<f64>>>(), Err(RufftError::EmptyInput));
+        let empty: Vec<Complex<f64>> = vec![];
+        assert_eq!(empty.try_ifft::<Vec<f64>>(), Err(RufftError::EmptyInput));
+        let x: Vec<f64> = vec![1.0, 2.0, 3.0];
+        assert_eq!(x.try_fft_n::<Vec<Complex<f64>>>(0), Err(RufftError::InvalidParameter("n")));
+        let spectrum: Vec<Complex<f64>> = x.try_fft().unwrap();
+        let y: Vec<f64> = spectrum.try_ifft().unwrap();
+        for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+            assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_complex_fft_matches_dft() {
+        for n in [16, 1000, 1001] {
+            let x: Vec<Complex<f64>> = (0..n)
+                .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+                .collect();
+            let reference: Vec<Complex<f64>> = fft::complex::dft(&x);
+            let spectrum: Vec<Complex<f64>> = x.complex_fft();
+            for (i, (&a, &b)) in spectrum.iter().zip(reference.iter()).enumerate() {
+                assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                    "n = {}, {} => {} != {}", n, i, a, b);
+            }
+            for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+                let spectrum: Vec<Complex<f64>> = x.complex_fft_norm(norm);
+                let y: Vec<Complex<f64>> = spectrum.complex_ifft_norm(norm);
+                for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                    assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), 
+                        "n = {}, {} => {} != {}", n, i, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_fft_arr_to_vec() {
+        let x: ndarray::Array1<Complex<f64>> = (0..12).map(|i| Complex::new(i as f64, 1.0)).collect();
+        let spectrum: Vec<Complex<f64>> = x.complex_fft();
+        let y: ndarray::Array1<Complex<f64>> = spectrum.complex_ifft();
+        for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+            assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+        let empty: Vec<Complex<f64>> = vec![];
+        assert_eq!(empty.try_complex_fft::<Vec<Complex<f64>>>(), Err(RufftError::EmptyInput));
+    }
+
+    #[test]
+    fn test_fft_signal_inputs() {
+        let x: Vec<f64> = (0..12).map(|i| (i as f64 * 0.3).sin()).collect();
+        let reference: Vec<Complex<f64>> = x.fft();
+        let check = |output: Vec<Complex<f64>>| {
+            for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+                assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+            }
+        };
+
+        let slice: &[f64] = &x;
+        check(slice.fft());
+        let array: [f64; 12] = x.clone().try_into().unwrap();
+        check(array.fft());
+        let boxed: Box<[f64]> = x.clone().into_boxed_slice();
+        check(boxed.fft());
+        let deque: std::collections::VecDeque<f64> = x.iter().cloned().collect();
+        check(deque.fft());
+        let arr = ndarray::Array1::from(x.clone());
+        check(arr.view().fft());
+        let mut arr = arr;
+        check(arr.view_mut().fft());
+        check(crate::fft::czt::fft(slice));
+    }
+
+    #[test]
+    fn test_ifft_signal_inputs() {
+        let x: Vec<f64> = (0..12).map(|i| (i as f64 * 0.3).sin()).collect();
+        let spectrum: Vec<Complex<f64>> = x.fft();
+        let check = |output: Vec<f64>| {
+            for (i, (&a, &b)) in output.iter().zip(x.iter()).enumerate() {
+                assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+            }
+        };
+
+        let slice: &[Complex<f64>] = &spectrum;
+        check(slice.ifft());
+        let arr = ndarray::Array1::from(spectrum.clone());
+        check(arr.view().ifft());
+        check(arr.ifft());
+    }
+
+    #[test]
+    fn test_complex_fft_view_input() {
+        let x: ndarray::Array1<Complex<f64>> = (0..20).map(|i| Complex::new(i as f64, -1.0)).collect();
+        // A view with a negative stride
+        let view = x.slice(ndarray::s![..;-1]);
+        let output: Vec<Complex<f64>> = view.complex_fft();
+        let reversed: Vec<Complex<f64>> = x.iter().rev().cloned().collect();
+        let reference: Vec<Complex<f64>> = fft::complex::dft(&reversed);
+        for (i, (&a, &b)) in output.iter().zip(reference.iter()).enumerate() {
+            assert!(test::nearly_equal_complex(a, b, RTOL_F64, ATOL_F64), "{} => {} != {}", i, a, b);
+        }
+    }
+
+    #[test]
+    fn test_fft_ifft_norm_roundtrip() {
+        for n in [16, 1000, 1001] {
+            let x: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin()).collect();
+            let backward: Vec<Complex<f64>> = x.fft();
+            for norm in [Normalization::Backward, Normalization::Ortho, Normalization::Forward] {
+                let spectrum: Vec<Complex<f64>> = x.fft_norm(norm);
+                let factor: f64 = norm.forward_scale(n);
+                for (i, (&a, &b)) in spectrum.iter().zip(backward.iter()).enumerate() {
+                    assert!(test::nearly_equal_complex(a, b * factor, RTOL_F64, ATOL_F64), 
+                        "n = {}, {} => {} != {}", n, i, a, b * factor);
+                }
+                let y: Vec<f64> = spectrum.ifft_norm(norm);
+                for (i, (&a, &b)) in y.iter().zip(x.iter()).enumerate() {
+                    assert!(test::nearly_equal(a, b, RTOL_F64, ATOL_F64), 
+                        "n = {}, {} => {} != {}", n, i, a, b);
+                }
+            }
+        }
+    }
+}
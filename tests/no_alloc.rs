@@ -0,0 +1,70 @@
+//! Checks that the transforms on caller provided buffers never allocate, also
+//! with the default `std` feature. The global allocator counts the allocations
+//! of the calling thread so other threads of the test harness are ignored
+use std::alloc::{ GlobalAlloc, Layout, System };
+use std::cell::Cell;
+use rufft::Complex;
+use rufft::fft::{ self, real };
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations made by `f` on the calling thread
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(|count| count.get());
+    f();
+    ALLOCATIONS.with(|count| count.get()) - before
+}
+
+#[test]
+fn test_in_place_transforms_do_not_allocate() {
+    // Powers of two, mixed-radix lengths and Bluestein's algorithm
+    for n in [64, 60, 67] {
+        let mut buf: Vec<Complex<f64>> = (0..n).map(|i| Complex::new((i as f64 * 0.3).sin(), 0.0)).collect();
+        let mut scratch = vec![Complex::new(0.0, 0.0); fft::scratch_len(n)];
+        let count = allocations(|| {
+            fft::fft_in_place_with_scratch(&mut buf, &mut scratch).unwrap();
+            fft::ifft_in_place_with_scratch(&mut buf, &mut scratch).unwrap();
+        });
+        assert_eq!(count, 0, "n = {}", n);
+        if n.is_power_of_two() {
+            let count = allocations(|| {
+                fft::fft_in_place(&mut buf).unwrap();
+                fft::ifft_in_place(&mut buf).unwrap();
+            });
+            assert_eq!(count, 0, "n = {}", n);
+        }
+    }
+}
+
+#[test]
+fn test_real_transforms_do_not_allocate() {
+    for n in [64, 60, 67] {
+        let x: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin()).collect();
+        let mut out = vec![Complex::new(0.0, 0.0); n / 2 + 1];
+        let mut y = vec![0.0; n];
+        let mut scratch = vec![Complex::new(0.0, 0.0); real::rfft_scratch_len(n).max(real::irfft_scratch_len(n))];
+        let count = allocations(|| {
+            real::rfft_into(&x, &mut out, &mut scratch).unwrap();
+            real::irfft_into(&out, &mut y, &mut scratch).unwrap();
+        });
+        assert_eq!(count, 0, "n = {}", n);
+    }
+}